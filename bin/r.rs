@@ -1,10 +1,19 @@
-use std::{fs::File, io::Read};
+use aoc2024::inputs::InputStore;
+use aoc2024::inputs::YEAR;
 
 fn main() {
-    let mut input = String::new();
-    let _ = File::open("input/2024/day20.txt")
-        .unwrap()
-        .read_to_string(&mut input);
+    let mut args = std::env::args().skip(1);
+    let profile = match (args.next().as_deref(), args.next()) {
+        (Some("--profile"), Some(profile)) => Some(profile),
+        (None, _) => None,
+        _ => {
+            eprintln!("Usage: r [--profile NAME]");
+            std::process::exit(2);
+        }
+    };
+    let input = InputStore::default()
+        .read(YEAR, 20, profile.as_deref())
+        .unwrap();
     let input = &aoc2024::day20::input_generator(&input);
     for _ in 0..1000 {
         aoc2024::day20::solver_part2_slice(input);
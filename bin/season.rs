@@ -0,0 +1,113 @@
+use std::io;
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use aoc2024::inputs::InputStore;
+use aoc2024::inputs::Validation;
+use aoc2024::inputs::YEAR;
+use aoc2024::season;
+
+const USAGE: &str =
+    "Usage: season [--day N] [--no-variants] [--budget SECS] [--profile NAME].. [--inputs]";
+
+fn main() {
+    let mut days = None;
+    let mut variants = true;
+    let mut limit = season::VARIANT_BUDGET;
+    let mut profiles = Vec::new();
+    let mut check_inputs = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let day = args.next().and_then(|d| d.parse::<u8>().ok());
+                days = Some(day.expect("--day takes a number from 1 to 25"));
+            }
+            "--no-variants" => variants = false,
+            "--budget" => {
+                let secs = args.next().and_then(|s| s.parse::<f64>().ok());
+                limit = Duration::from_secs_f64(secs.expect("--budget takes a number of seconds"));
+            }
+            "--profile" => profiles.push(Some(args.next().expect("--profile takes a name"))),
+            "--inputs" => check_inputs = true,
+            _ => {
+                eprintln!("{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+    if profiles.is_empty() {
+        profiles.push(None);
+    }
+
+    // Solver panics are reported in the table
+    season::quiet_solver_panics();
+    // Some solvers recurse deeply
+    let report = thread::Builder::new()
+        .stack_size(1 << 30)
+        .spawn(move || {
+            let store = InputStore::default();
+            let all = season::days();
+            let mut out = String::new();
+            // Shared by every profile, an overrun variant keeps running to the end
+            let mut budget = variants.then(|| season::Budget::new(limit));
+            for profile in profiles {
+                let profile = profile.as_deref();
+                out += &format!("== {YEAR} {} ==\n", profile.unwrap_or("default"));
+                if check_inputs {
+                    out += &inputs_report(&store, profile, &all);
+                    continue;
+                }
+                let path = store.answers_path(YEAR, profile);
+                let answers = season::Answers::load(&path).unwrap_or_else(|e| fail(&path, e));
+                let mut reports = all
+                    .iter()
+                    .filter(|d| days.is_none_or(|day| d.day == day))
+                    .map(|day| {
+                        let input = match store.read(YEAR, day.day, profile) {
+                            Ok(input) => Some(input),
+                            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+                            Err(e) => fail(&store.path(YEAR, day.day, profile), e),
+                        };
+                        season::run_day(day, input.as_deref(), &answers, budget.as_mut())
+                    })
+                    .collect::<Vec<_>>();
+                if days.is_none() {
+                    season::award_free_stars(&mut reports, &all);
+                }
+                out += &season::render(&reports);
+            }
+            if let Some((day, part, name)) = budget.and_then(|b| b.overrun()) {
+                out += &format!(
+                    "Stopped timing variants once day {day} part {part} {name} ran past {limit:?}\n"
+                );
+            }
+            out
+        })
+        .expect("Failed to start the season thread")
+        .join()
+        // The panic hook has already printed why
+        .unwrap_or_else(|_| process::exit(101));
+    print!("{report}");
+}
+
+fn fail(path: &Path, e: io::Error) -> ! {
+    eprintln!("Can't read {}: {e}", path.display());
+    process::exit(1);
+}
+
+fn inputs_report(store: &InputStore, profile: Option<&str>, days: &[season::Day]) -> String {
+    let mut out = String::new();
+    for (day, validation) in store.validate(YEAR, profile, days) {
+        let status = match validation {
+            Validation::Valid => "ok".to_string(),
+            Validation::Missing => format!("missing {}", store.path(YEAR, day, profile).display()),
+            Validation::Unreadable(e) => format!("unreadable: {e}"),
+            Validation::Invalid(e) => format!("fails to parse: {e}"),
+        };
+        out += &format!("Day {day:>2}: {status}\n");
+    }
+    out
+}
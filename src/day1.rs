@@ -1,5 +1,11 @@
+use std::collections::BTreeMap;
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::str::FromStr;
 use std::thread;
 
 use aoc_runner_derive::aoc;
@@ -9,7 +15,7 @@ use itertools::Itertools;
 type Input = String;
 
 #[aoc_generator(day1)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -112,7 +118,7 @@ fn parse_heap(input: &str) -> (BinaryHeap<i32>, BinaryHeap<i32>) {
 }
 
 #[aoc(day1, part1)]
-fn solver_part1(input: &Input) -> i64 {
+pub(crate) fn solver_part1(input: &Input) -> i64 {
     let mut input = parse(input);
     input.0.sort();
     input.1.sort();
@@ -128,7 +134,7 @@ fn solver_part1(input: &Input) -> i64 {
 }
 
 #[aoc(day1, part1, UNSTABLE)]
-fn solver_part1_unstable(input: &Input) -> i64 {
+pub(crate) fn solver_part1_unstable(input: &Input) -> i64 {
     let mut input = parse(input);
     input.0.sort_unstable();
     input.1.sort_unstable();
@@ -144,7 +150,7 @@ fn solver_part1_unstable(input: &Input) -> i64 {
 }
 
 #[aoc(day1, part1, BHEAP)]
-fn solver_part1_bheap(input: &Input) -> i32 {
+pub(crate) fn solver_part1_bheap(input: &Input) -> i32 {
     let heap = parse_heap(input);
     heap.0
         .into_sorted_vec()
@@ -155,7 +161,7 @@ fn solver_part1_bheap(input: &Input) -> i32 {
 }
 
 #[aoc(day1, part1, UNSTABLE_I32)]
-fn solver_part1_unstable_i32(input: &Input) -> i32 {
+pub(crate) fn solver_part1_unstable_i32(input: &Input) -> i32 {
     let mut input = parse_i32(input);
     input.0.sort_unstable();
     input.1.sort_unstable();
@@ -171,7 +177,7 @@ fn solver_part1_unstable_i32(input: &Input) -> i32 {
 }
 
 #[aoc(day1, part1, UNSTABLE_I32_CUSTOM_PARSE)]
-fn solver_part1_unstable_i32_custom_parse(input: &Input) -> i32 {
+pub(crate) fn solver_part1_unstable_i32_custom_parse(input: &Input) -> i32 {
     let mut input = custom_parse(input);
     input.0.sort_unstable();
     input.1.sort_unstable();
@@ -187,7 +193,7 @@ fn solver_part1_unstable_i32_custom_parse(input: &Input) -> i32 {
 }
 
 #[aoc(day1, part1, UNSTABLE_I32_CUSTOM_PARSE_2)]
-fn solver_part1_unstable_i32_custom_parse_2(input: &Input) -> i32 {
+pub(crate) fn solver_part1_unstable_i32_custom_parse_2(input: &Input) -> i32 {
     let mut input = custom_parse_2(input);
     input.0.sort_unstable();
     input.1.sort_unstable();
@@ -203,7 +209,7 @@ fn solver_part1_unstable_i32_custom_parse_2(input: &Input) -> i32 {
 }
 
 #[aoc(day1, part1, UNSTABLE_I32_PARALLEL)]
-fn solver_part1_unstable_i32_parallel(input: &Input) -> i32 {
+pub(crate) fn solver_part1_unstable_i32_parallel(input: &Input) -> i32 {
     let (mut a, mut b) = parse_i32(input);
     let handle = thread::spawn(|| {
         a.sort_unstable();
@@ -221,7 +227,7 @@ fn solver_part1_unstable_i32_parallel(input: &Input) -> i32 {
 }
 
 #[aoc(day1, part1, UNSTABLE_I32_FAST)]
-fn solver_part1_unstable_i32_fast(input: &Input) -> i32 {
+pub(crate) fn solver_part1_unstable_i32_fast(input: &Input) -> i32 {
     let mut input = fast_parse_i32(input);
     input.0.sort_unstable();
     input.1.sort_unstable();
@@ -254,7 +260,7 @@ fn parse_map(input: &str) -> (Vec<i32>, HashMap<i32, i32>) {
 }
 
 #[aoc(day1, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let input = parse(input);
     input
         .0
@@ -264,7 +270,7 @@ fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day1, part2, HASH)]
-fn solver_part2_hash(input: &Input) -> i64 {
+pub(crate) fn solver_part2_hash(input: &Input) -> i64 {
     let input = parse(input);
     let mut hash = HashMap::new();
     for v in &input.1 {
@@ -288,7 +294,7 @@ fn solver_part2_hash(input: &Input) -> i64 {
 }
 
 #[aoc(day1, part2, HASH_INLINE)]
-fn solver_part2_hash_inline(input: &Input) -> i32 {
+pub(crate) fn solver_part2_hash_inline(input: &Input) -> i32 {
     let input = parse_map(input);
 
     input
@@ -303,10 +309,184 @@ fn solver_part2_hash_inline(input: &Input) -> i32 {
         })
         .sum()
 }
+
+pub trait LocationId: Copy + Ord + FromStr {
+    fn distance(self, other: Self) -> u64;
+    fn widen(self) -> i128;
+}
+
+impl LocationId for i64 {
+    fn distance(self, other: Self) -> u64 {
+        self.abs_diff(other)
+    }
+
+    fn widen(self) -> i128 {
+        self as i128
+    }
+}
+
+impl LocationId for u64 {
+    fn distance(self, other: Self) -> u64 {
+        self.abs_diff(other)
+    }
+
+    fn widen(self) -> i128 {
+        self as i128
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListDiff<T> {
+    pub left_only: Vec<(T, u64)>,
+    pub right_only: Vec<(T, u64)>,
+}
+
+// Both lists kept as sorted counts, so pairs can be added a few at a time without holding the input
+#[derive(Debug, Clone)]
+pub struct LocationLists<T> {
+    left: BTreeMap<T, u64>,
+    right: BTreeMap<T, u64>,
+}
+
+impl<T: LocationId> LocationLists<T> {
+    pub fn new() -> Self {
+        LocationLists {
+            left: BTreeMap::new(),
+            right: BTreeMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, left: T, right: T) {
+        *self.left.entry(left).or_insert(0) += 1;
+        *self.right.entry(right).or_insert(0) += 1;
+    }
+
+    // Reads whitespace separated pairs line by line, returning how many were added
+    pub fn ingest<R: Read>(&mut self, reader: R) -> io::Result<usize> {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        let mut count = 0;
+        while reader.read_line(&mut line)? != 0 {
+            let mut ids = line.split_ascii_whitespace();
+            match (ids.next(), ids.next(), ids.next()) {
+                (None, _, _) => (),
+                (Some(a), Some(b), None) => match (a.parse(), b.parse()) {
+                    (Ok(a), Ok(b)) => {
+                        self.push(a, b);
+                        count += 1;
+                    }
+                    _ => return Err(invalid_line(&line)),
+                },
+                _ => return Err(invalid_line(&line)),
+            }
+            line.clear();
+        }
+        Ok(count)
+    }
+
+    pub fn len(&self) -> (u64, u64) {
+        (self.left.values().sum(), self.right.values().sum())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
+    }
+
+    // Sum of gaps between the smallest of each list, then the next smallest and so on. Stops
+    // at the end of the shorter list
+    pub fn distance(&self) -> u128 {
+        let mut left = self.left.iter().map(|(&id, &n)| (id, n));
+        let mut right = self.right.iter().map(|(&id, &n)| (id, n));
+        let (mut a, mut b) = (left.next(), right.next());
+        let mut total = 0;
+        while let (Some((x, n)), Some((y, m))) = (a, b) {
+            let paired = n.min(m);
+            total += x.distance(y) as u128 * paired as u128;
+            a = if n == paired {
+                left.next()
+            } else {
+                Some((x, n - paired))
+            };
+            b = if m == paired {
+                right.next()
+            } else {
+                Some((y, m - paired))
+            };
+        }
+        total
+    }
+
+    // Each left id times the number of times it appears on the right
+    pub fn similarity(&self) -> i128 {
+        self.left
+            .iter()
+            .filter_map(|(id, &n)| {
+                self.right
+                    .get(id)
+                    .map(|&m| id.widen() * n as i128 * m as i128)
+            })
+            .sum()
+    }
+
+    // Ids with more copies on one side than the other, along with the excess
+    pub fn diff(&self) -> ListDiff<T> {
+        let excess = |a: &BTreeMap<T, u64>, b: &BTreeMap<T, u64>| {
+            a.iter()
+                .filter_map(|(&id, &n)| {
+                    let extra = n.saturating_sub(b.get(&id).copied().unwrap_or(0));
+                    (extra > 0).then_some((id, extra))
+                })
+                .collect()
+        };
+        ListDiff {
+            left_only: excess(&self.left, &self.right),
+            right_only: excess(&self.right, &self.left),
+        }
+    }
+}
+
+impl<T: LocationId> Default for LocationLists<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn invalid_line(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid location pair {:?}", line.trim_end()),
+    )
+}
+
+#[aoc(day1, part1, STREAM)]
+pub(crate) fn solver_part1_stream(input: &Input) -> u128 {
+    let mut lists = LocationLists::<i64>::new();
+    lists.ingest(input.as_bytes()).unwrap();
+    lists.distance()
+}
+
+#[aoc(day1, part2, STREAM)]
+pub(crate) fn solver_part2_stream(input: &Input) -> i128 {
+    let mut lists = LocationLists::<i64>::new();
+    lists.ingest(input.as_bytes()).unwrap();
+    lists.similarity()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_stream;
+    use super::solver_part2_stream;
+    use super::ListDiff;
+    use super::LocationLists;
+
+    static INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
 
     #[test]
     fn sample1() {
@@ -322,4 +502,41 @@ mod tests {
             11
         )
     }
+
+    #[test]
+    fn stream() {
+        assert_eq!(solver_part1_stream(&input_generator(INPUT)), 11);
+        assert_eq!(solver_part2_stream(&input_generator(INPUT)), 31);
+
+        // Feed the lines in two separate reads
+        let (first, second) = INPUT.split_at(12);
+        let mut lists = LocationLists::<u64>::new();
+        assert_eq!(lists.ingest(first.as_bytes()).unwrap(), 2);
+        assert_eq!(lists.ingest(second.as_bytes()).unwrap(), 4);
+        assert_eq!(lists.len(), (6, 6));
+        assert_eq!(lists.distance(), 11);
+        assert_eq!(
+            lists.diff(),
+            ListDiff {
+                left_only: vec![(1, 1), (2, 1)],
+                right_only: vec![(5, 1), (9, 1)],
+            }
+        );
+    }
+
+    #[test]
+    fn wide_ids() {
+        let mut lists = LocationLists::<u64>::new();
+        lists.push(u64::MAX, 0);
+        lists.push(u64::MAX, u64::MAX);
+        assert_eq!(lists.distance(), u64::MAX as u128);
+        assert_eq!(lists.similarity(), 2 * u64::MAX as i128);
+
+        let mut lists = LocationLists::<i64>::new();
+        lists.ingest("-5 7\n-5 -5\n".as_bytes()).unwrap();
+        assert_eq!(lists.distance(), 12);
+        assert_eq!(lists.similarity(), -10);
+        assert!(lists.ingest("1 2 3\n".as_bytes()).is_err());
+        assert!(lists.ingest("1 x\n".as_bytes()).is_err());
+    }
 }
@@ -8,11 +8,17 @@ use fxhash::FxHashSet;
 
 type Input = Grid<u8>;
 
+// Height given to `.` tiles, which can never be stepped on
+pub const IMPASSABLE: u8 = u8::MAX;
+
 #[aoc_generator(day10)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     let width = input.lines().next().unwrap().len();
     Grid::from_iter(
-        &mut input.lines().flat_map(|l| l.bytes().map(|b| b - 48)),
+        &mut input.lines().flat_map(|l| {
+            l.bytes()
+                .map(|b| if b == b'.' { IMPASSABLE } else { b - 48 })
+        }),
         width,
     )
 }
@@ -33,7 +39,7 @@ fn find_path(coord: &Coord, next: u8, grid: &Grid<u8>) -> HashSet<Coord> {
 }
 
 #[aoc(day10, part1)]
-fn solver_part1(grid: &Input) -> usize {
+pub(crate) fn solver_part1(grid: &Input) -> usize {
     grid.iter()
         .filter(|(_, &i)| i == 0)
         .map(|(c, _)| find_path(&c, 1, grid).len())
@@ -66,7 +72,7 @@ fn find_path_cache(
 }
 
 #[aoc(day10, part1, CACHE)]
-fn solver_part1_cache(grid: &Input) -> usize {
+pub(crate) fn solver_part1_cache(grid: &Input) -> usize {
     let mut cache: Grid<Option<HashSet<Coord>>> =
         Grid::from_iter(&mut grid.iter().map(|_| None), grid.width);
     grid.iter()
@@ -101,7 +107,7 @@ fn find_path_fxcache(
 }
 
 #[aoc(day10, part1, CACHE_FX)]
-fn solver_part1_cache_fx(grid: &Input) -> usize {
+pub(crate) fn solver_part1_cache_fx(grid: &Input) -> usize {
     let mut cache: Grid<Option<FxHashSet<Coord>>> =
         Grid::from_iter(&mut grid.iter().map(|_| None), grid.width);
     grid.iter()
@@ -134,7 +140,7 @@ fn find_path_fxcache_vec(
 }
 
 #[aoc(day10, part1, CACHE_FX_VEC)]
-fn solver_part1_cache_fx_vec(grid: &Input) -> usize {
+pub(crate) fn solver_part1_cache_fx_vec(grid: &Input) -> usize {
     let mut cache: Grid<Option<Vec<Coord>>> =
         Grid::from_iter(&mut grid.iter().map(|_| None), grid.width);
     grid.iter()
@@ -162,7 +168,7 @@ fn find_path_2(coord: &Coord, next: u8, grid: &Grid<u8>) -> usize {
 }
 
 #[aoc(day10, part2)]
-fn solver_part2(grid: &Input) -> usize {
+pub(crate) fn solver_part2(grid: &Input) -> usize {
     grid.iter()
         .filter(|(_, &i)| i == 0)
         .map(|(c, _)| find_path_2(&c, 1, grid))
@@ -193,7 +199,7 @@ fn find_path_2_cache(
 }
 
 #[aoc(day10, part2, CACHE)]
-fn solver_part2_cache(grid: &Input) -> usize {
+pub(crate) fn solver_part2_cache(grid: &Input) -> usize {
     let mut cache: Grid<Option<usize>> =
         Grid::from_iter(&mut grid.iter().map(|_| None), grid.width);
     grid.iter()
@@ -202,6 +208,116 @@ fn solver_part2_cache(grid: &Input) -> usize {
         .sum()
 }
 
+fn climb(from: u8, to: u8) -> bool {
+    from.checked_add(1) == Some(to)
+}
+
+// Enumerates trails between two heights, following a rule for which height steps are allowed
+pub struct TrailFinder<'a, F> {
+    grid: &'a Grid<u8>,
+    start: u8,
+    end: u8,
+    step: F,
+}
+
+impl<'a> TrailFinder<'a, fn(u8, u8) -> bool> {
+    // Puzzle rules, climbing one height at a time from 0 to 9
+    pub fn new(grid: &'a Grid<u8>) -> Self {
+        TrailFinder {
+            grid,
+            start: 0,
+            end: 9,
+            step: climb,
+        }
+    }
+}
+
+impl<'a, F: Fn(u8, u8) -> bool> TrailFinder<'a, F> {
+    pub fn with_heights(mut self, start: u8, end: u8) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    pub fn with_step<G: Fn(u8, u8) -> bool>(self, step: G) -> TrailFinder<'a, G> {
+        TrailFinder {
+            grid: self.grid,
+            start: self.start,
+            end: self.end,
+            step,
+        }
+    }
+
+    pub fn trailheads(&self) -> Vec<Coord> {
+        self.grid
+            .iter()
+            .filter(|(_, &h)| h == self.start)
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    // Up to `limit` distinct trails from the trailhead, each from start to end height
+    pub fn trails(&self, trailhead: Coord, limit: usize) -> Vec<Vec<Coord>> {
+        let mut found = Vec::new();
+        if self.grid.checked_index(&trailhead) == Some(&self.start) && limit > 0 {
+            self.walk(&mut vec![trailhead], &mut found, limit);
+        }
+        found
+    }
+
+    fn walk(&self, path: &mut Vec<Coord>, found: &mut Vec<Vec<Coord>>, limit: usize) {
+        let coord = *path.last().unwrap();
+        let height = self.grid[coord];
+        if height == self.end {
+            found.push(path.clone());
+            return;
+        }
+        for (next, &h) in self.grid.orthogs_coords(&coord).into_iter().flatten() {
+            // Step rules may allow level ground, so never walk back over the trail
+            if h != IMPASSABLE && (self.step)(height, h) && !path.contains(&next) {
+                path.push(next);
+                self.walk(path, found, limit);
+                path.pop();
+                if found.len() >= limit {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Every trailhead with its trails, at most `limit` each
+    pub fn all_trails(&self, limit: usize) -> Vec<(Coord, Vec<Vec<Coord>>)> {
+        self.trailheads()
+            .into_iter()
+            .map(|head| (head, self.trails(head, limit)))
+            .collect()
+    }
+}
+
+#[aoc(day10, part1, TRAILS)]
+pub(crate) fn solver_part1_trails(grid: &Input) -> usize {
+    TrailFinder::new(grid)
+        .all_trails(usize::MAX)
+        .iter()
+        .map(|(_, trails)| {
+            trails
+                .iter()
+                .map(|t| t.last().unwrap())
+                .collect::<HashSet<_>>()
+                .len()
+        })
+        .sum()
+}
+
+#[aoc(day10, part2, TRAILS)]
+pub(crate) fn solver_part2_trails(grid: &Input) -> usize {
+    TrailFinder::new(grid)
+        .all_trails(usize::MAX)
+        .iter()
+        .map(|(_, trails)| trails.len())
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day10::solver_part1_cache;
@@ -211,7 +327,11 @@ mod tests {
 
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_trails;
     use super::solver_part2;
+    use super::solver_part2_trails;
+    use super::TrailFinder;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "89010123
 78121874
@@ -250,4 +370,61 @@ mod tests {
     fn part2_cache() {
         assert_eq!(solver_part2_cache(&input_generator(INPUT)), 81)
     }
+
+    #[test]
+    fn part1_trails() {
+        assert_eq!(solver_part1_trails(&input_generator(INPUT)), 36)
+    }
+
+    #[test]
+    fn part2_trails() {
+        assert_eq!(solver_part2_trails(&input_generator(INPUT)), 81)
+    }
+
+    #[test]
+    fn impassable() {
+        let grid = input_generator(
+            "...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9",
+        );
+        assert_eq!(solver_part1(&grid), 2);
+        let trails = TrailFinder::new(&grid).trails(Coord::new(3, 0), 10);
+        assert_eq!(trails.len(), 2);
+        assert_eq!(trails[0].len(), 10);
+        assert_eq!(trails[0][9], Coord::new(6, 6));
+
+        let grid = input_generator(
+            ".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....",
+        );
+        assert_eq!(solver_part2_trails(&grid), 3);
+        assert_eq!(TrailFinder::new(&grid).trails(Coord::new(5, 0), 2).len(), 2);
+    }
+
+    #[test]
+    fn rules() {
+        let grid = input_generator(INPUT);
+        let descending = TrailFinder::new(&grid)
+            .with_heights(9, 0)
+            .with_step(|from, to| to + 1 == from);
+        let count: usize = descending
+            .all_trails(usize::MAX)
+            .iter()
+            .map(|(_, t)| t.len())
+            .sum();
+        assert_eq!(count, 81);
+
+        let short = TrailFinder::new(&grid).with_heights(0, 1);
+        assert_eq!(short.trails(Coord::new(2, 0), 10).len(), 2);
+    }
 }
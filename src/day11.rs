@@ -1,16 +1,21 @@
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
 use std::time::Instant;
 
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
 use fxhash::FxHashMap;
 use itertools::Itertools;
+use num::BigUint;
+use num::One;
+use num::Zero;
 use rayon::prelude::*;
 
 type Input = String;
 
 #[aoc_generator(day11)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -146,7 +151,7 @@ fn split_vec(vec: &[u64]) -> Vec<u64> {
     new
 }
 #[aoc(day11, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     input
         .split_ascii_whitespace()
         .map(|str| str.parse::<u64>().unwrap())
@@ -155,7 +160,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day11, part1, BFS)]
-fn solver_part1_bfs(input: &Input) -> usize {
+pub(crate) fn solver_part1_bfs(input: &Input) -> usize {
     bfs(
         25,
         input
@@ -166,7 +171,7 @@ fn solver_part1_bfs(input: &Input) -> usize {
 }
 
 #[aoc(day11, part1, Parallel)]
-fn solver_part1_parallel(input: &Input) -> usize {
+pub(crate) fn solver_part1_parallel(input: &Input) -> usize {
     input
         .split_ascii_whitespace()
         .map(|str| str.parse::<u64>().unwrap())
@@ -176,7 +181,7 @@ fn solver_part1_parallel(input: &Input) -> usize {
 }
 
 #[aoc(day11, part1, FX_Custom)]
-fn solver_part1_fx_custom(input: &Input) -> usize {
+pub(crate) fn solver_part1_fx_custom(input: &Input) -> usize {
     let mut cache: [_; 25] = core::array::from_fn(|_| FxHashMap::default());
     input
         .split_ascii_whitespace()
@@ -186,7 +191,7 @@ fn solver_part1_fx_custom(input: &Input) -> usize {
 }
 
 #[aoc(day11, part1, Parallel_FX)]
-fn solver_part1_parallel_fx(input: &Input) -> usize {
+pub(crate) fn solver_part1_parallel_fx(input: &Input) -> usize {
     let cache = FxHashMap::default();
     input
         .split_ascii_whitespace()
@@ -197,7 +202,7 @@ fn solver_part1_parallel_fx(input: &Input) -> usize {
 }
 
 #[aoc(day11, part1, Parallel_FX_Custom)]
-fn solver_part1_parallel_fx_custom(input: &Input) -> usize {
+pub(crate) fn solver_part1_parallel_fx_custom(input: &Input) -> usize {
     let cache: [_; 25] = core::array::from_fn(|_| FxHashMap::default());
     input
         .split_ascii_whitespace()
@@ -208,7 +213,7 @@ fn solver_part1_parallel_fx_custom(input: &Input) -> usize {
 }
 
 #[aoc(day11, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let mut cache = HashMap::new();
     input
         .split_ascii_whitespace()
@@ -234,7 +239,7 @@ fn solver_part2(input: &Input) -> usize {
 // }
 
 #[aoc(day11, part2, Parallel)]
-fn solver_part2_parallel(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel(input: &Input) -> usize {
     let mut vec = input
         .split_ascii_whitespace()
         .map(|str| str.parse::<u64>().unwrap())
@@ -258,7 +263,7 @@ fn solver_part2_parallel(input: &Input) -> usize {
 }
 
 #[aoc(day11, part2, Parallel_Cache)]
-fn solver_part2_parallel_cache(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_cache(input: &Input) -> usize {
     let cache = HashMap::new();
     input
         .split_ascii_whitespace()
@@ -269,7 +274,7 @@ fn solver_part2_parallel_cache(input: &Input) -> usize {
 }
 
 #[aoc(day11, part2, FX_Custom)]
-fn solver_part2_fx_custom(input: &Input) -> usize {
+pub(crate) fn solver_part2_fx_custom(input: &Input) -> usize {
     let mut cache: [_; 75] = core::array::from_fn(|_| FxHashMap::default());
     input
         .split_ascii_whitespace()
@@ -279,7 +284,7 @@ fn solver_part2_fx_custom(input: &Input) -> usize {
 }
 
 #[aoc(day11, part2, Parallel_FX)]
-fn solver_part2_parallel_fx(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_fx(input: &Input) -> usize {
     let cache = FxHashMap::default();
     input
         .split_ascii_whitespace()
@@ -290,7 +295,7 @@ fn solver_part2_parallel_fx(input: &Input) -> usize {
 }
 
 #[aoc(day11, part2, Parallel_FX_Custom)]
-fn solver_part2_parallel_fx_custom(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_fx_custom(input: &Input) -> usize {
     let cache: [_; 75] = core::array::from_fn(|_| FxHashMap::default());
     input
         .split_ascii_whitespace()
@@ -300,11 +305,136 @@ fn solver_part2_parallel_fx_custom(input: &Input) -> usize {
         .sum()
 }
 
+type StoneRule<V> = Box<dyn Fn(&V) -> Option<Vec<V>>>;
+
+// Blink rules tried in order, the first to return stones replaces the stone.
+// A stone no rule applies to is left as it is.
+pub struct StoneRules<V> {
+    rules: Vec<StoneRule<V>>,
+}
+
+impl<V: Clone + Eq + Hash> StoneRules<V> {
+    pub fn new() -> Self {
+        StoneRules { rules: Vec::new() }
+    }
+
+    pub fn with_rule(mut self, rule: impl Fn(&V) -> Option<Vec<V>> + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    fn blink_stone(&self, stone: &V) -> Vec<V> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule(stone))
+            .unwrap_or_else(|| vec![stone.clone()])
+    }
+
+    pub fn frequencies<C: Clone + Zero + One>(stones: &[V]) -> FxHashMap<V, C> {
+        let mut map = FxHashMap::default();
+        for stone in stones {
+            let count: &mut C = map.entry(stone.clone()).or_insert_with(C::zero);
+            *count = count.clone() + C::one();
+        }
+        map
+    }
+
+    // Steps the `value -> count` map, each distinct value is only transformed once per blink
+    pub fn blink<C: Clone + Zero>(&self, stones: &FxHashMap<V, C>) -> FxHashMap<V, C> {
+        let mut next = FxHashMap::default();
+        for (stone, count) in stones {
+            for new in self.blink_stone(stone) {
+                let total: &mut C = next.entry(new).or_insert_with(C::zero);
+                *total = total.clone() + count.clone();
+            }
+        }
+        next
+    }
+
+    pub fn evolve<C: Clone + Zero>(
+        &self,
+        mut stones: FxHashMap<V, C>,
+        blinks: usize,
+    ) -> FxHashMap<V, C> {
+        for _ in 0..blinks {
+            stones = self.blink(&stones);
+        }
+        stones
+    }
+
+    pub fn count<C: Clone + Zero + One>(&self, stones: &[V], blinks: usize) -> C {
+        self.evolve(Self::frequencies(stones), blinks)
+            .into_values()
+            .fold(C::zero(), |sum, count| sum + count)
+    }
+}
+
+impl<V: Clone + Eq + Hash> Default for StoneRules<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StoneRules<u128> {
+    pub fn puzzle() -> Self {
+        StoneRules::new()
+            .with_rule(|&v: &u128| (v == 0).then(|| vec![1]))
+            .with_rule(|&v: &u128| {
+                let digits = v.ilog10() + 1;
+                digits.is_multiple_of(2).then(|| {
+                    let pow = 10u128.pow(digits / 2);
+                    vec![v / pow, v % pow]
+                })
+            })
+            .with_rule(|&v: &u128| Some(vec![v.checked_mul(2024).expect("Stone overflowed u128")]))
+    }
+}
+
+impl StoneRules<BigUint> {
+    pub fn puzzle() -> Self {
+        StoneRules::new()
+            .with_rule(|v: &BigUint| v.is_zero().then(|| vec![BigUint::one()]))
+            .with_rule(|v: &BigUint| {
+                let digits = v.to_string();
+                digits.len().is_multiple_of(2).then(|| {
+                    let (left, right) = digits.split_at(digits.len() / 2);
+                    vec![left.parse().unwrap(), right.parse().unwrap()]
+                })
+            })
+            .with_rule(|v: &BigUint| Some(vec![v * 2024u32]))
+    }
+}
+
+pub fn parse_stones<V: FromStr>(input: &str) -> Vec<V>
+where
+    V::Err: std::fmt::Debug,
+{
+    input
+        .split_ascii_whitespace()
+        .map(|str| str.parse().unwrap())
+        .collect()
+}
+
+#[aoc(day11, part1, RULES)]
+pub(crate) fn solver_part1_rules(input: &Input) -> u128 {
+    StoneRules::<u128>::puzzle().count(&parse_stones(input), 25)
+}
+
+#[aoc(day11, part2, RULES)]
+pub(crate) fn solver_part2_rules(input: &Input) -> u128 {
+    StoneRules::<u128>::puzzle().count(&parse_stones(input), 75)
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
+    use super::parse_stones;
     use super::solver_part1;
+    use super::solver_part1_rules;
     use super::solver_part2;
+    use super::solver_part2_rules;
+    use super::StoneRules;
+    use num::BigUint;
 
     static INPUT: &str = "125 17";
 
@@ -317,6 +447,39 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 0)
     }
+
+    #[test]
+    fn part1_rules() {
+        assert_eq!(solver_part1_rules(&input_generator(INPUT)), 55312)
+    }
+
+    #[test]
+    fn part2_rules() {
+        assert_eq!(solver_part2_rules(&input_generator(INPUT)), 65601038650482)
+    }
+
+    #[test]
+    fn big() {
+        let small = StoneRules::<u128>::puzzle().count::<u128>(&parse_stones(INPUT), 150);
+        let big = StoneRules::<BigUint>::puzzle().count::<BigUint>(&parse_stones(INPUT), 150);
+        assert_eq!(big, BigUint::from(small));
+
+        // Counts far past what fits in a u128
+        let big = StoneRules::<u128>::puzzle().count::<BigUint>(&parse_stones(INPUT), 500);
+        assert_eq!(big.to_string().len(), 91);
+    }
+
+    #[test]
+    fn custom_rules() {
+        // Even stones halve, odd stones split into two of one less
+        let rules = StoneRules::new()
+            .with_rule(|&v: &u64| (v % 2 == 0).then(|| vec![v / 2]))
+            .with_rule(|&v: &u64| (v > 1).then(|| vec![v - 1, v - 1]));
+        let stones = rules.evolve::<u64>(StoneRules::frequencies(&[6]), 3);
+        assert_eq!(stones.get(&1), Some(&2));
+        assert_eq!(rules.count::<u64>(&[6], 3), 2);
+        assert_eq!(rules.count::<u64>(&[7], 2), 2);
+    }
 }
 // Parallel
 // 25, 189547, 0.002321947 secs
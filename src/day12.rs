@@ -10,7 +10,7 @@ use itertools::Itertools;
 type Input = String;
 
 #[aoc_generator(day12)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -59,7 +59,7 @@ fn find_region_lines(
 }
 
 #[aoc(day12, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let width = input.lines().next().unwrap().len();
     let grid = Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width);
     let mut searched = HashSet::default();
@@ -80,7 +80,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day12, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let width = input.lines().next().unwrap().len();
     let grid = Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width);
     let mut vertical = Grid::new(0u8, grid.width, grid.height);
@@ -164,11 +164,122 @@ fn solver_part2(input: &Input) -> usize {
     // 0
 }
 
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub plant: u8,
+    pub cells: Vec<Coord>,
+    pub perimeter: usize,
+    pub sides: usize,
+    // Bounding box as the top left and bottom right cells
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn price(&self) -> usize {
+        self.area() * self.perimeter
+    }
+
+    pub fn bulk_price(&self) -> usize {
+        self.area() * self.sides
+    }
+}
+
+// Flood fills each region with an explicit stack so large maps cannot overflow
+pub fn find_regions(grid: &Grid<u8>) -> Vec<Region> {
+    let mut ids = Grid::new(usize::MAX, grid.width, grid.height);
+    let mut regions = Vec::new();
+    for (start, &plant) in grid.iter() {
+        if ids[start] != usize::MAX {
+            continue;
+        }
+        let id = regions.len();
+        ids[start] = id;
+        let mut stack = vec![start];
+        let mut cells = Vec::new();
+        while let Some(coord) = stack.pop() {
+            cells.push(coord);
+            for (next, &plant2) in grid.orthogs_coords(&coord).into_iter().flatten() {
+                if plant2 == plant && ids[next] == usize::MAX {
+                    ids[next] = id;
+                    stack.push(next);
+                }
+            }
+        }
+        regions.push(Region {
+            plant,
+            min: Coord::new(
+                cells.iter().map(|c| c.x).min().unwrap(),
+                cells.iter().map(|c| c.y).min().unwrap(),
+            ),
+            max: Coord::new(
+                cells.iter().map(|c| c.x).max().unwrap(),
+                cells.iter().map(|c| c.y).max().unwrap(),
+            ),
+            cells,
+            perimeter: 0,
+            sides: 0,
+        });
+    }
+
+    for region in regions.iter_mut() {
+        let id = ids[region.cells[0]];
+        let same = |c: &Coord| ids.checked_index(c) == Some(&id);
+        for coord in &region.cells {
+            region.perimeter += coord.orthogs().iter().filter(|c| !same(c)).count();
+            // A region has as many sides as corners
+            for (a, b, diag) in [
+                (coord.north(), coord.east(), coord.north_east()),
+                (coord.east(), coord.south(), coord.south_east()),
+                (coord.south(), coord.west(), coord.south_west()),
+                (coord.west(), coord.north(), coord.north_west()),
+            ] {
+                let outer = !same(&a) && !same(&b);
+                let inner = same(&a) && same(&b) && !same(&diag);
+                if outer || inner {
+                    region.sides += 1;
+                }
+            }
+        }
+    }
+    regions
+}
+
+pub(crate) fn parse_grid(input: &str) -> Grid<u8> {
+    let width = input.lines().next().unwrap().len();
+    Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width)
+}
+
+#[aoc(day12, part1, REGIONS)]
+pub(crate) fn solver_part1_regions(input: &Input) -> usize {
+    find_regions(&parse_grid(input))
+        .iter()
+        .map(Region::price)
+        .sum()
+}
+
+#[aoc(day12, part2, REGIONS)]
+pub(crate) fn solver_part2_regions(input: &Input) -> usize {
+    find_regions(&parse_grid(input))
+        .iter()
+        .map(Region::bulk_price)
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::find_regions;
     use super::input_generator;
+    use super::parse_grid;
     use super::solver_part1;
+    use super::solver_part1_regions;
     use super::solver_part2;
+    use super::solver_part2_regions;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "RRRRIICCFF
 RRRRIICCCF
@@ -190,4 +301,51 @@ MMMISSJEEE";
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 1206)
     }
+
+    #[test]
+    fn part1_regions() {
+        assert_eq!(solver_part1_regions(&input_generator(INPUT)), 1930)
+    }
+
+    #[test]
+    fn part2_regions() {
+        assert_eq!(solver_part2_regions(&input_generator(INPUT)), 1206)
+    }
+
+    #[test]
+    fn breakdown() {
+        let regions = find_regions(&parse_grid("AAAA\nBBCD\nBBCC\nEEEC"));
+        assert_eq!(
+            regions
+                .iter()
+                .map(|r| (r.plant, r.area(), r.perimeter, r.sides))
+                .collect::<Vec<_>>(),
+            vec![
+                (b'A', 4, 10, 4),
+                (b'B', 4, 8, 4),
+                (b'C', 4, 10, 8),
+                (b'D', 1, 4, 4),
+                (b'E', 3, 8, 4)
+            ]
+        );
+        assert_eq!(
+            (regions[2].min, regions[2].max),
+            (Coord::new(2, 1), Coord::new(3, 3))
+        );
+
+        // Region inside another counts the inner fence too
+        let regions = find_regions(&parse_grid("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"));
+        assert_eq!(
+            (regions[0].area(), regions[0].perimeter, regions[0].sides),
+            (21, 36, 20)
+        );
+    }
+
+    #[test]
+    fn large() {
+        let input = vec!["A".repeat(500); 500].join("\n");
+        let regions = find_regions(&parse_grid(&input));
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].perimeter, 2000);
+    }
 }
@@ -2,6 +2,7 @@
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
 use itertools::Itertools;
+use num::integer::ExtendedGcd;
 
 type Input = Vec<[(u32, u32); 3]>;
 
@@ -16,7 +17,7 @@ fn parse_prize(str: &str) -> (u32, u32) {
     )
 }
 #[aoc_generator(day13)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input
         .lines()
         .chunks(4)
@@ -32,7 +33,7 @@ fn input_generator(input: &str) -> Input {
 }
 
 #[aoc(day13, part1)]
-fn solver_part1(input: &Input) -> u32 {
+pub(crate) fn solver_part1(input: &Input) -> u32 {
     input
         .iter()
         .filter_map(|arr| {
@@ -50,17 +51,14 @@ fn solver_part1(input: &Input) -> u32 {
                         None
                     }
                 }
-                None => {
-                    dbg!(A);
-                    None
-                }
+                None => None,
             }
         })
         .sum()
 }
 
 #[aoc(day13, part2)]
-fn solver_part2(input: &Input) -> u64 {
+pub(crate) fn solver_part2(input: &Input) -> u64 {
     input
         .iter()
         .filter_map(|arr| {
@@ -85,11 +83,121 @@ fn solver_part2(input: &Input) -> u64 {
         .sum()
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Pricing {
+    pub cost_a: i128,
+    pub cost_b: i128,
+    pub offset: i128,
+}
+
+impl Pricing {
+    pub const PART1: Pricing = Pricing {
+        cost_a: 3,
+        cost_b: 1,
+        offset: 0,
+    };
+    pub const PART2: Pricing = Pricing {
+        cost_a: 3,
+        cost_b: 1,
+        offset: 10000000000000,
+    };
+}
+
+// Cheapest non-negative (a, b) with a * u + b * v = p, where u and v are not both zero
+fn cheapest_1d(u: i128, v: i128, p: i128, pricing: &Pricing) -> Option<(i128, i128)> {
+    if u == 0 {
+        return (p % v == 0 && p / v >= 0).then_some((0, p / v));
+    }
+    if v == 0 {
+        return (p % u == 0 && p / u >= 0).then_some((p / u, 0));
+    }
+    // u * x + v * y = g
+    let ExtendedGcd { gcd: g, x, y, .. } = num::Integer::extended_gcd(&u, &v);
+    if p % g != 0 {
+        return None;
+    }
+    let (a0, b0) = (x * (p / g), y * (p / g));
+    let (step_a, step_b) = (v / g, u / g);
+    // a = a0 + k * step_a >= 0 and b = b0 - k * step_b >= 0
+    let k_min = num::Integer::div_ceil(&-a0, &step_a);
+    let k_max = num::Integer::div_floor(&b0, &step_b);
+    if k_min > k_max {
+        return None;
+    }
+    // cost is linear in k so the cheapest is at one of the ends
+    let k = if pricing.cost_a * step_a <= pricing.cost_b * step_b {
+        k_max
+    } else {
+        k_min
+    };
+    Some((a0 + k * step_a, b0 - k * step_b))
+}
+
+// Exact number of (A, B) presses that reaches the prize for the fewest tokens
+pub fn solve_machine(machine: &[(u32, u32); 3], pricing: &Pricing) -> Option<(i128, i128)> {
+    let [(ax, ay), (bx, by), (px, py)] = machine.map(|(x, y)| (x as i128, y as i128));
+    let (px, py) = (px + pricing.offset, py + pricing.offset);
+
+    let det = ax * by - bx * ay;
+    if det != 0 {
+        // Cramer's rule
+        let a = px * by - bx * py;
+        let b = ax * py - px * ay;
+        if a % det != 0 || b % det != 0 {
+            return None;
+        }
+        let (a, b) = (a / det, b / det);
+        return (a >= 0 && b >= 0).then_some((a, b));
+    }
+
+    // Buttons are collinear, so the prize must lie on the same line
+    let (dx, dy) = if (ax, ay) != (0, 0) {
+        (ax, ay)
+    } else {
+        (bx, by)
+    };
+    if dx * py != dy * px {
+        return None;
+    }
+    if (dx, dy) == (0, 0) {
+        return (px == 0 && py == 0).then_some((0, 0));
+    }
+    if dx != 0 {
+        cheapest_1d(ax, bx, px, pricing)
+    } else {
+        cheapest_1d(ay, by, py, pricing)
+    }
+}
+
+pub fn min_tokens(machine: &[(u32, u32); 3], pricing: &Pricing) -> Option<i128> {
+    solve_machine(machine, pricing).map(|(a, b)| a * pricing.cost_a + b * pricing.cost_b)
+}
+
+#[aoc(day13, part1, EXACT)]
+pub(crate) fn solver_part1_exact(input: &Input) -> i128 {
+    input
+        .iter()
+        .filter_map(|m| min_tokens(m, &Pricing::PART1))
+        .sum()
+}
+
+#[aoc(day13, part2, EXACT)]
+pub(crate) fn solver_part2_exact(input: &Input) -> i128 {
+    input
+        .iter()
+        .filter_map(|m| min_tokens(m, &Pricing::PART2))
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
+    use super::min_tokens;
     use super::solver_part1;
+    use super::solver_part1_exact;
     use super::solver_part2;
+    use super::solver_part2_exact;
+    use super::Pricing;
 
     static INPUT: &str = "Button A: X+94, Y+34
 Button B: X+22, Y+67
@@ -116,4 +224,39 @@ Prize: X=18641, Y=10279";
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 875318608908)
     }
+
+    #[test]
+    fn part1_exact() {
+        assert_eq!(solver_part1_exact(&input_generator(INPUT)), 480)
+    }
+
+    #[test]
+    fn part2_exact() {
+        assert_eq!(solver_part2_exact(&input_generator(INPUT)), 875318608908)
+    }
+
+    #[test]
+    fn collinear() {
+        // B is cheaper per unit distance
+        assert_eq!(
+            min_tokens(&[(2, 2), (3, 3), (12, 12)], &Pricing::PART1),
+            Some(4)
+        );
+        // A is cheaper per unit distance
+        assert_eq!(
+            min_tokens(&[(4, 4), (1, 1), (10, 10)], &Pricing::PART1),
+            Some(8)
+        );
+        // Off the line
+        assert_eq!(
+            min_tokens(&[(2, 2), (3, 3), (12, 13)], &Pricing::PART1),
+            None
+        );
+        // Not reachable with non-negative presses
+        assert_eq!(min_tokens(&[(4, 4), (6, 6), (3, 3)], &Pricing::PART1), None);
+        assert_eq!(
+            min_tokens(&[(0, 0), (0, 5), (0, 15)], &Pricing::PART1),
+            Some(3)
+        );
+    }
 }
@@ -1,11 +1,12 @@
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
 use itertools::Itertools;
+use num::integer::ExtendedGcd;
 
 type Input = String;
 
 #[aoc_generator(day14)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -33,7 +34,7 @@ fn step(pos: &mut (i32, i32), vel: &(i32, i32)) {
 }
 
 #[aoc(day14, part1)]
-fn solver_part1(input: &Input) -> u32 {
+pub(crate) fn solver_part1(input: &Input) -> u32 {
     input
         .lines()
         .fold([0, 0, 0, 0], |mut q, l| {
@@ -63,7 +64,7 @@ fn solver_part1(input: &Input) -> u32 {
 }
 
 #[aoc(day14, part1, REM)]
-fn solver_part1_rem(input: &Input) -> u32 {
+pub(crate) fn solver_part1_rem(input: &Input) -> u32 {
     input
         .lines()
         .fold([0, 0, 0, 0], |mut q, l| {
@@ -91,7 +92,7 @@ fn solver_part1_rem(input: &Input) -> u32 {
 }
 
 #[aoc(day14, part2)]
-fn solver_part2(input: &Input) -> u32 {
+pub(crate) fn solver_part2(input: &Input) -> u32 {
     let mut bots = input.lines().map(parse_line).collect_vec();
     let mut steps = 0;
     loop {
@@ -118,26 +119,13 @@ fn solver_part2(input: &Input) -> u32 {
             .filter(|&count| count > 10)
             .count();
         if row_count > 5 {
-            // check for tree
-            println!("===============================================================");
-            for row in grid {
-                for cell in row {
-                    if cell {
-                        print!("#");
-                    } else {
-                        print!(" ");
-                    }
-                }
-                println!();
-            }
-            println!();
             return steps;
         }
     }
 }
 
 #[aoc(day14, part2, BORDER)]
-fn solver_part2_border(input: &Input) -> u32 {
+pub(crate) fn solver_part2_border(input: &Input) -> u32 {
     let mut bots = input.lines().map(parse_line).collect_vec();
     let mut steps = 0;
     loop {
@@ -164,10 +152,219 @@ fn solver_part2_border(input: &Input) -> u32 {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Robot {
+    pub pos: (i32, i32),
+    pub vel: (i32, i32),
+}
+
+// Scores how ordered a histogram of robot counts along one axis looks, lower being more ordered
+pub trait AnomalyScore {
+    fn score(&self, counts: &[usize]) -> f64;
+}
+
+pub struct Variance;
+
+impl AnomalyScore for Variance {
+    fn score(&self, counts: &[usize]) -> f64 {
+        let total = counts.iter().sum::<usize>() as f64;
+        let mean = counts
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| i as f64 * n as f64)
+            .sum::<f64>()
+            / total;
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (i as f64 - mean).powi(2) * n as f64)
+            .sum::<f64>()
+            / total
+    }
+}
+
+pub struct Entropy;
+
+impl AnomalyScore for Entropy {
+    fn score(&self, counts: &[usize]) -> f64 {
+        let total = counts.iter().sum::<usize>() as f64;
+        counts
+            .iter()
+            .filter(|&&n| n > 0)
+            .map(|&n| {
+                let p = n as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+// Longest stretch of adjacent rows or columns that each hold more robots than average
+pub struct RunLength;
+
+impl AnomalyScore for RunLength {
+    fn score(&self, counts: &[usize]) -> f64 {
+        let total = counts.iter().sum::<usize>();
+        let (max, current) = counts.iter().fold((0, 0), |(max, current), &n| {
+            if n * counts.len() > total {
+                (max, current + 1)
+            } else {
+                (max.max(current), 0)
+            }
+        });
+        -(max.max(current) as f64)
+    }
+}
+
+// Smallest t >= 0 with t = a1 (mod m1) and t = a2 (mod m2), if the two agree
+pub fn crt(a1: i64, m1: i64, a2: i64, m2: i64) -> Option<i64> {
+    let ExtendedGcd { gcd: g, x: p, .. } = num::Integer::extended_gcd(&m1, &m2);
+    if (a2 - a1) % g != 0 {
+        return None;
+    }
+    let lcm = m1 / g * m2;
+    let k = ((a2 - a1) / g % (m2 / g) * p).rem_euclid(m2 / g);
+    Some((a1 + m1 * k).rem_euclid(lcm))
+}
+
+pub struct Room {
+    width: i32,
+    height: i32,
+    robots: Vec<Robot>,
+}
+
+impl Room {
+    pub fn new(width: i32, height: i32, robots: Vec<Robot>) -> Self {
+        Room {
+            width,
+            height,
+            robots,
+        }
+    }
+
+    pub fn parse(input: &str, width: i32, height: i32) -> Self {
+        let robots = input
+            .lines()
+            .map(parse_line)
+            .map(|(pos, vel)| Robot { pos, vel })
+            .collect();
+        Room::new(width, height, robots)
+    }
+
+    fn wrap(pos: i32, vel: i32, size: i32, t: u64) -> i32 {
+        let t = (t % size as u64) as i64;
+        (pos as i64 + vel as i64 * t).rem_euclid(size as i64) as i32
+    }
+
+    pub fn positions_at(&self, t: u64) -> Vec<(i32, i32)> {
+        self.robots
+            .iter()
+            .map(|r| {
+                (
+                    Room::wrap(r.pos.0, r.vel.0, self.width, t),
+                    Room::wrap(r.pos.1, r.vel.1, self.height, t),
+                )
+            })
+            .collect()
+    }
+
+    pub fn safety_factor(&self, t: u64) -> usize {
+        let (mid_x, mid_y) = (self.width / 2, self.height / 2);
+        self.positions_at(t)
+            .into_iter()
+            .fold([0; 4], |mut q, (x, y)| {
+                if x != mid_x && y != mid_y {
+                    q[(x > mid_x) as usize * 2 + (y > mid_y) as usize] += 1;
+                }
+                q
+            })
+            .iter()
+            .product()
+    }
+
+    // Robot counts per tile in the puzzle's notation, counts above 9 show as 9
+    pub fn render(&self, t: u64) -> String {
+        let mut counts = vec![0u32; (self.width * self.height) as usize];
+        for (x, y) in self.positions_at(t) {
+            counts[(y * self.width + x) as usize] += 1;
+        }
+        counts
+            .chunks(self.width as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|&n| match n {
+                        0 => '.',
+                        n => char::from_digit(n.min(9), 10).unwrap(),
+                    })
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    // The time in 0..size at which the axis histogram scores lowest. Each axis repeats with its own size
+    fn best_phase(
+        &self,
+        size: i32,
+        axis: impl Fn(&Robot) -> (i32, i32),
+        score: &impl AnomalyScore,
+    ) -> i64 {
+        (0..size as u64)
+            .map(|t| {
+                let mut counts = vec![0; size as usize];
+                for robot in &self.robots {
+                    let (pos, vel) = axis(robot);
+                    counts[Room::wrap(pos, vel, size, t) as usize] += 1;
+                }
+                (t, score.score(&counts))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap()
+            .0 as i64
+    }
+
+    // First frame where both axes look most ordered, found per axis then combined with the CRT
+    pub fn find_picture(&self, score: &impl AnomalyScore) -> Option<u64> {
+        let x = self.best_phase(self.width, |r| (r.pos.0, r.vel.0), score);
+        let y = self.best_phase(self.height, |r| (r.pos.1, r.vel.1), score);
+        crt(x, self.width as i64, y, self.height as i64).map(|t| t as u64)
+    }
+}
+
+#[aoc(day14, part1, ROOM)]
+pub(crate) fn solver_part1_room(input: &Input) -> usize {
+    Room::parse(input, 101, 103).safety_factor(100)
+}
+
+#[aoc(day14, part2, VARIANCE)]
+pub(crate) fn solver_part2_variance(input: &Input) -> u64 {
+    Room::parse(input, 101, 103)
+        .find_picture(&Variance)
+        .unwrap()
+}
+
+#[aoc(day14, part2, ENTROPY)]
+pub(crate) fn solver_part2_entropy(input: &Input) -> u64 {
+    Room::parse(input, 101, 103).find_picture(&Entropy).unwrap()
+}
+
+#[aoc(day14, part2, RUN_LENGTH)]
+pub(crate) fn solver_part2_run_length(input: &Input) -> u64 {
+    Room::parse(input, 101, 103)
+        .find_picture(&RunLength)
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::crt;
     use super::input_generator;
     use super::solver_part1;
+    use super::Entropy;
+    use super::Robot;
+    use super::Room;
+    use super::RunLength;
+    use super::Variance;
 
     static INPUT: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -186,4 +383,72 @@ p=9,5 v=-3,-3";
     fn part1() {
         assert_eq!(solver_part1(&input_generator(INPUT)), 12)
     }
+
+    #[test]
+    fn room() {
+        let room = Room::parse(INPUT, 11, 7);
+        assert_eq!(room.safety_factor(100), 12);
+        assert_eq!(
+            room.render(100),
+            "\
+......2..1.
+...........
+1..........
+.11........
+.....1.....
+...12......
+.1....1....
+"
+        );
+        assert_eq!(room.positions_at(100 + 77), room.positions_at(100));
+    }
+
+    #[test]
+    fn crt_combine() {
+        assert_eq!(crt(2, 3, 3, 5), Some(8));
+        assert_eq!(crt(68, 101, 17, 103), Some(7845));
+        assert_eq!(crt(1, 4, 3, 6), Some(9));
+        assert_eq!(crt(1, 4, 2, 6), None);
+    }
+
+    #[test]
+    fn picture() {
+        // A 4x4 block with three strays at t = 20, wound back to t = 0
+        let (width, height, t) = (11, 13, 20);
+        let frame = [
+            ((4, 4), (6, 5)),
+            ((5, 4), (5, -2)),
+            ((6, 4), (2, -3)),
+            ((7, 4), (5, 1)),
+            ((4, 5), (2, 1)),
+            ((5, 5), (-1, -4)),
+            ((6, 5), (1, -2)),
+            ((7, 5), (3, -1)),
+            ((4, 6), (6, 4)),
+            ((5, 6), (5, 4)),
+            ((6, 6), (-5, 2)),
+            ((7, 6), (4, -1)),
+            ((4, 7), (2, 1)),
+            ((5, 7), (4, 1)),
+            ((6, 7), (-4, -2)),
+            ((7, 7), (-3, -5)),
+            ((1, 10), (2, 3)),
+            ((9, 1), (-5, 1)),
+            ((0, 3), (3, 6)),
+        ];
+        let robots = frame
+            .iter()
+            .map(|&(pos, vel): &((i32, i32), (i32, i32))| Robot {
+                pos: (
+                    (pos.0 - vel.0 * t).rem_euclid(width),
+                    (pos.1 - vel.1 * t).rem_euclid(height),
+                ),
+                vel,
+            })
+            .collect();
+        let room = Room::new(width, height, robots);
+        assert_eq!(room.find_picture(&Variance), Some(t as u64));
+        assert_eq!(room.find_picture(&Entropy), Some(t as u64));
+        assert_eq!(room.find_picture(&RunLength), Some(t as u64));
+    }
 }
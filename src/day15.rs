@@ -7,7 +7,7 @@ use itertools::Itertools;
 type Input = String;
 
 #[aoc_generator(day15)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -43,7 +43,7 @@ fn parse_input_2(input: &str) -> (Grid<u8>, std::str::Lines<'_>) {
 }
 
 #[aoc(day15, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let (mut grid, lines) = parse_input(input);
     // find start pos
     let mut bot = Coord::new(0, 0);
@@ -234,7 +234,7 @@ fn move_down(grid: &mut Grid<u8>, mut vec: Vec<Coord>) {
 }
 
 #[aoc(day15, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let (mut grid, lines) = parse_input_2(input);
     // find start pos
     let mut bot = Coord::new(0, 0);
@@ -373,11 +373,284 @@ fn solver_part2(input: &Input) -> usize {
         .sum()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn from_byte(b: u8) -> Option<Direction> {
+        match b {
+            b'^' => Some(Direction::Up),
+            b'v' => Some(Direction::Down),
+            b'<' => Some(Direction::Left),
+            b'>' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    fn forward(self, coord: Coord) -> Coord {
+        match self {
+            Direction::Up => coord.north(),
+            Direction::Down => coord.south(),
+            Direction::Left => coord.west(),
+            Direction::Right => coord.east(),
+        }
+    }
+
+    fn back(self, coord: Coord) -> Coord {
+        match self {
+            Direction::Up => coord.south(),
+            Direction::Down => coord.north(),
+            Direction::Left => coord.east(),
+            Direction::Right => coord.west(),
+        }
+    }
+}
+
+// A single robot move, `pushed` holds the indices of every box it shifted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub direction: Direction,
+    pub robot: Coord,
+    pub moved: bool,
+    pub pushed: Vec<usize>,
+}
+
+// Boxes are stored by their left-most cell, `cells` maps every covered cell back to its box
+pub struct Warehouse {
+    walls: Grid<bool>,
+    cells: Grid<Option<usize>>,
+    boxes: Vec<Coord>,
+    box_width: usize,
+    robot: Coord,
+    history: Vec<Step>,
+    redo: Vec<Step>,
+}
+
+impl Warehouse {
+    // Parses the map and moves, stretching every map tile to `box_width` cells
+    pub fn parse(input: &str, box_width: usize) -> (Warehouse, Vec<Direction>) {
+        let mut lines = input.lines();
+        let map = lines
+            .take_while_ref(|l| !l.is_empty())
+            .map(|l| l.as_bytes())
+            .collect_vec();
+        let width = map[0].len() * box_width;
+        let mut walls = Grid::new(false, width, map.len());
+        let mut cells = Grid::new(None, width, map.len());
+        let mut boxes = Vec::new();
+        let mut robot = Coord::new(0, 0);
+        for (y, row) in map.iter().enumerate() {
+            for (x, b) in row.iter().enumerate() {
+                let c = Coord::new((x * box_width) as i32, y as i32);
+                match b {
+                    b'#' => {
+                        for dx in 0..box_width {
+                            walls[Coord::new(c.x + dx as i32, c.y)] = true;
+                        }
+                    }
+                    b'O' => {
+                        for dx in 0..box_width {
+                            cells[Coord::new(c.x + dx as i32, c.y)] = Some(boxes.len());
+                        }
+                        boxes.push(c);
+                    }
+                    b'@' => robot = c,
+                    _ => (),
+                }
+            }
+        }
+        let moves = lines
+            .flat_map(|l| l.bytes())
+            .filter_map(Direction::from_byte)
+            .collect();
+        let warehouse = Warehouse {
+            walls,
+            cells,
+            boxes,
+            box_width,
+            robot,
+            history: Vec::new(),
+            redo: Vec::new(),
+        };
+        (warehouse, moves)
+    }
+
+    pub fn robot(&self) -> Coord {
+        self.robot
+    }
+
+    pub fn boxes(&self) -> &[Coord] {
+        &self.boxes
+    }
+
+    pub fn history(&self) -> &[Step] {
+        &self.history
+    }
+
+    fn box_cells(&self, index: usize) -> impl Iterator<Item = Coord> {
+        let start = self.boxes[index];
+        (0..self.box_width as i32).map(move |dx| Coord::new(start.x + dx, start.y))
+    }
+
+    // Every box that would be shoved by moving the robot, or None if a wall is in the way
+    fn pushed_boxes(&self, direction: Direction) -> Option<Vec<usize>> {
+        let mut pushed = Vec::new();
+        let mut seen = vec![false; self.boxes.len()];
+        let mut stack = vec![direction.forward(self.robot)];
+        while let Some(c) = stack.pop() {
+            if self.walls[c] {
+                return None;
+            }
+            if let Some(index) = self.cells[c] {
+                if !seen[index] {
+                    seen[index] = true;
+                    pushed.push(index);
+                    stack.extend(self.box_cells(index).map(|c| direction.forward(c)));
+                }
+            }
+        }
+        Some(pushed)
+    }
+
+    fn shift(&mut self, pushed: &[usize], step: impl Fn(Coord) -> Coord) {
+        for &index in pushed {
+            for c in self.box_cells(index).collect_vec() {
+                self.cells[c] = None;
+            }
+        }
+        for &index in pushed {
+            self.boxes[index] = step(self.boxes[index]);
+            for c in self.box_cells(index).collect_vec() {
+                self.cells[c] = Some(index);
+            }
+        }
+    }
+
+    fn apply(&mut self, direction: Direction) -> Step {
+        let robot = self.robot;
+        match self.pushed_boxes(direction) {
+            Some(pushed) => {
+                self.shift(&pushed, |c| direction.forward(c));
+                self.robot = direction.forward(robot);
+                Step {
+                    direction,
+                    robot,
+                    moved: true,
+                    pushed,
+                }
+            }
+            None => Step {
+                direction,
+                robot,
+                moved: false,
+                pushed: Vec::new(),
+            },
+        }
+    }
+
+    // Moves the robot, returning whether it moved. Clears anything that could be redone
+    pub fn step(&mut self, direction: Direction) -> bool {
+        self.redo.clear();
+        let step = self.apply(direction);
+        let moved = step.moved;
+        self.history.push(step);
+        moved
+    }
+
+    pub fn run(&mut self, moves: &[Direction]) {
+        for &direction in moves {
+            self.step(direction);
+        }
+    }
+
+    pub fn undo(&mut self) -> Option<&Step> {
+        let step = self.history.pop()?;
+        if step.moved {
+            self.shift(&step.pushed, |c| step.direction.back(c));
+            self.robot = step.robot;
+        }
+        self.redo.push(step);
+        self.redo.last()
+    }
+
+    pub fn redo(&mut self) -> Option<&Step> {
+        let step = self.redo.pop()?;
+        let step = self.apply(step.direction);
+        self.history.push(step);
+        self.history.last()
+    }
+
+    // Undoes or redoes until exactly `steps` moves have been made
+    pub fn seek(&mut self, steps: usize) {
+        while self.history.len() > steps && self.undo().is_some() {}
+        while self.history.len() < steps && self.redo().is_some() {}
+    }
+
+    pub fn gps_sum(&self) -> usize {
+        self.boxes
+            .iter()
+            .map(|c| c.x as usize + 100 * c.y as usize)
+            .sum()
+    }
+
+    // Width 1 boxes render as "O", wider ones as "[]", "[=]", "[==]" and so on
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for y in 0..self.walls.height as i32 {
+            for x in 0..self.walls.width as i32 {
+                let c = Coord::new(x, y);
+                out.push(if c == self.robot {
+                    '@'
+                } else if self.walls[c] {
+                    '#'
+                } else if let Some(index) = self.cells[c] {
+                    let offset = x - self.boxes[index].x;
+                    if self.box_width == 1 {
+                        'O'
+                    } else if offset == 0 {
+                        '['
+                    } else if offset == self.box_width as i32 - 1 {
+                        ']'
+                    } else {
+                        '='
+                    }
+                } else {
+                    '.'
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[aoc(day15, part1, SIMULATOR)]
+pub(crate) fn solver_part1_simulator(input: &Input) -> usize {
+    let (mut warehouse, moves) = Warehouse::parse(input, 1);
+    warehouse.run(&moves);
+    warehouse.gps_sum()
+}
+
+#[aoc(day15, part2, SIMULATOR)]
+pub(crate) fn solver_part2_simulator(input: &Input) -> usize {
+    let (mut warehouse, moves) = Warehouse::parse(input, 2);
+    warehouse.run(&moves);
+    warehouse.gps_sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_simulator;
     use super::solver_part2;
+    use super::solver_part2_simulator;
+    use super::Warehouse;
 
     static INPUT: &str = "##########
 #..O..O.O#
@@ -511,4 +784,77 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
     fn part2_8() {
         assert_eq!(solver_part2(&input_generator(INPUT8)), 816)
     }
+
+    #[test]
+    fn simulator() {
+        let part1 = [(INPUT, 10092), (INPUT2, 2028)];
+        for (input, expected) in part1 {
+            assert_eq!(solver_part1_simulator(&input_generator(input)), expected);
+        }
+        let part2 = [
+            (INPUT, 9021),
+            (INPUT3, 618),
+            (INPUT4, 822),
+            (INPUT5, 406),
+            (INPUT6, 509),
+            (INPUT7, 511),
+            (INPUT8, 816),
+        ];
+        for (input, expected) in part2 {
+            assert_eq!(solver_part2_simulator(&input_generator(input)), expected);
+        }
+    }
+
+    #[test]
+    fn undo_replay() {
+        let (mut warehouse, moves) = Warehouse::parse(INPUT, 2);
+        let mut renders = vec![warehouse.render()];
+        for &direction in &moves {
+            warehouse.step(direction);
+            renders.push(warehouse.render());
+        }
+        assert_eq!(warehouse.gps_sum(), 9021);
+
+        for expected in renders.iter().rev().skip(1) {
+            warehouse.undo();
+            assert_eq!(&warehouse.render(), expected);
+        }
+        assert!(warehouse.undo().is_none());
+
+        warehouse.seek(100);
+        assert_eq!(warehouse.render(), renders[100]);
+        warehouse.seek(moves.len());
+        assert_eq!(warehouse.gps_sum(), 9021);
+    }
+
+    #[test]
+    fn wide_boxes() {
+        let (mut warehouse, moves) = Warehouse::parse(INPUT8, 3);
+        assert_eq!(
+            warehouse.render(),
+            "\
+##################
+###............###
+###...[=]......###
+###...[=][=]@..###
+###...[=]......###
+###............###
+##################
+"
+        );
+        warehouse.run(&moves);
+        assert!(warehouse.history().iter().all(|s| s.moved));
+        assert_eq!(
+            warehouse.render(),
+            "\
+##################
+###............###
+###...[=]......###
+###..[=][=]....###
+###...[=]@.....###
+###............###
+##################
+"
+        );
+    }
 }
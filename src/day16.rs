@@ -4,16 +4,18 @@ use aoc_tools::grid::Coord;
 use aoc_tools::grid::Grid;
 use fxhash::FxHashMap;
 use fxhash::FxHashSet;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
 type Input = String;
 
 #[aoc_generator(day16)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Orientation {
+pub enum Orientation {
     North,
     East,
     South,
@@ -141,7 +143,7 @@ fn mark_distances(grid: &Grid<u8>) -> [aoc_tools::grid::Grid<u32>; 4] {
 }
 
 #[aoc(day16, part1)]
-fn solver_part1(input: &Input) -> u32 {
+pub(crate) fn solver_part1(input: &Input) -> u32 {
     let width = input.lines().next().unwrap().len();
     let grid = Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width);
     let distances = mark_distances(&grid);
@@ -154,7 +156,7 @@ fn solver_part1(input: &Input) -> u32 {
 }
 
 #[aoc(day16, part1, MAP)]
-fn solver_part1_map(input: &Input) -> u32 {
+pub(crate) fn solver_part1_map(input: &Input) -> u32 {
     let width = input.lines().next().unwrap().len();
     let grid = Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width);
     let mut distances: [_; 4] =
@@ -213,7 +215,7 @@ fn solver_part1_map(input: &Input) -> u32 {
 }
 
 #[aoc(day16, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let width = input.lines().next().unwrap().len();
     let grid = Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width);
     let distances = mark_distances(&grid);
@@ -267,13 +269,201 @@ fn solver_part2(input: &Input) -> usize {
     path.len() + 1
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs {
+    pub step: u32,
+    pub turn: u32,
+    pub reverse: u32,
+}
+
+impl Costs {
+    pub const PUZZLE: Costs = Costs {
+        step: 1,
+        turn: 1000,
+        reverse: 2000,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazeError {
+    MissingStart,
+    MissingEnd,
+}
+
+type State = (Coord, Orientation);
+
+pub struct Maze {
+    grid: Grid<u8>,
+    start: Coord,
+    end: Coord,
+}
+
+impl Maze {
+    pub fn parse(input: &str) -> Result<Maze, MazeError> {
+        let width = input.lines().next().map_or(0, |l| l.len());
+        let grid = Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width);
+        let find = |tile| grid.iter().find(|(_, &b)| b == tile).map(|(c, _)| c);
+        let start = find(b'S').ok_or(MazeError::MissingStart)?;
+        let end = find(b'E').ok_or(MazeError::MissingEnd)?;
+        Ok(Maze { grid, start, end })
+    }
+
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    pub fn end(&self) -> Coord {
+        self.end
+    }
+
+    fn moves(&self, (pos, direction): State, costs: &Costs) -> Vec<(State, u32)> {
+        let mut moves = vec![((pos, direction.opposite()), costs.reverse)];
+        moves.extend(direction.adjacent().map(|a| ((pos, a), costs.turn)));
+        let forward = forwards(&pos, &direction);
+        if self
+            .grid
+            .checked_index(&forward)
+            .is_some_and(|&b| b != b'#')
+        {
+            moves.push(((forward, direction), costs.step));
+        }
+        moves
+    }
+
+    // Dijkstra from S facing east, keeping every predecessor that ties for the best cost.
+    // None when E can't be reached
+    pub fn solve(&self, costs: &Costs) -> Option<Solution> {
+        let start = (self.start, Orientation::East);
+        let mut dist = FxHashMap::default();
+        let mut preds: FxHashMap<State, Vec<State>> = FxHashMap::default();
+        let mut done = FxHashSet::default();
+        let mut queue = BinaryHeap::new();
+        dist.insert(start, 0);
+        queue.push(Reverse((0, start.0.x, start.0.y, start.1.as_index())));
+
+        while let Some(Reverse((distance, x, y, o))) = queue.pop() {
+            let state = (Coord::new(x, y), Orientation::from(o));
+            if !done.insert(state) {
+                continue;
+            }
+            for (next, cost) in self.moves(state, costs) {
+                if done.contains(&next) {
+                    continue;
+                }
+                let total = distance + cost;
+                let best = dist.entry(next).or_insert(u32::MAX);
+                if total < *best {
+                    *best = total;
+                    preds.insert(next, vec![state]);
+                    queue.push(Reverse((total, next.0.x, next.0.y, next.1.as_index())));
+                } else if total == *best {
+                    preds.entry(next).or_default().push(state);
+                }
+            }
+        }
+
+        let ends = (0..4)
+            .map(|o| (self.end, Orientation::from(o)))
+            .filter_map(|s| dist.get(&s).map(|&d| (s, d)))
+            .collect::<Vec<_>>();
+        let cost = ends.iter().map(|e| e.1).min()?;
+        Some(Solution {
+            cost,
+            start,
+            ends: ends
+                .into_iter()
+                .filter(|e| e.1 == cost)
+                .map(|e| e.0)
+                .collect(),
+            preds,
+        })
+    }
+
+    // The maze with each path tile drawn as the direction it's left in
+    pub fn render(&self, paths: &[Vec<State>]) -> String {
+        let mut grid = self.grid.items.clone();
+        for &(pos, direction) in paths.iter().flatten() {
+            let index = pos.y as usize * self.grid.width + pos.x as usize;
+            if pos != self.start && pos != self.end {
+                grid[index] = match direction {
+                    Orientation::North => b'^',
+                    Orientation::East => b'>',
+                    Orientation::South => b'v',
+                    Orientation::West => b'<',
+                };
+            }
+        }
+        grid.chunks(self.grid.width)
+            .map(|row| String::from_utf8_lossy(row) + "\n")
+            .collect()
+    }
+}
+
+pub struct Solution {
+    pub cost: u32,
+    start: State,
+    ends: Vec<State>,
+    preds: FxHashMap<State, Vec<State>>,
+}
+
+impl Solution {
+    // Every optimal path from start to end, including the states where it turns on the spot
+    pub fn paths(&self) -> Vec<Vec<State>> {
+        let mut paths = Vec::new();
+        let mut stack = self.ends.iter().map(|&e| vec![e]).collect::<Vec<_>>();
+        while let Some(path) = stack.pop() {
+            let last = *path.last().unwrap();
+            if last == self.start {
+                paths.push(path.into_iter().rev().collect());
+                continue;
+            }
+            for &prev in &self.preds[&last] {
+                let mut path = path.clone();
+                path.push(prev);
+                stack.push(path);
+            }
+        }
+        paths
+    }
+
+    // Tiles on any optimal path, without enumerating the paths themselves
+    pub fn tiles(&self) -> FxHashSet<Coord> {
+        let mut seen = FxHashSet::default();
+        let mut stack = self.ends.clone();
+        while let Some(state) = stack.pop() {
+            if seen.insert(state) {
+                stack.extend(self.preds.get(&state).into_iter().flatten());
+            }
+        }
+        seen.into_iter().map(|s| s.0).collect()
+    }
+}
+
+#[aoc(day16, part1, SOLVER)]
+pub(crate) fn solver_part1_solver(input: &Input) -> u32 {
+    let maze = Maze::parse(input).unwrap();
+    maze.solve(&Costs::PUZZLE).unwrap().cost
+}
+
+#[aoc(day16, part2, SOLVER)]
+pub(crate) fn solver_part2_solver(input: &Input) -> usize {
+    let maze = Maze::parse(input).unwrap();
+    maze.solve(&Costs::PUZZLE).unwrap().tiles().len()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day16::solver_part1_map;
 
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_solver;
     use super::solver_part2;
+    use super::solver_part2_solver;
+    use super::Costs;
+    use super::Maze;
+    use super::MazeError;
+    use super::Orientation;
 
     static INPUT: &str = "###############
 #.......#....E#
@@ -398,4 +588,69 @@ S..";
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 45)
     }
+
+    #[test]
+    fn solver() {
+        let part1 = [
+            (INPUT, 7036),
+            (INPUT2, 11048),
+            (INPUT3, 21148),
+            (INPUT4, 4013),
+            (INPUT5, 21110),
+            (INPUT6, 1005),
+        ];
+        for (input, expected) in part1 {
+            assert_eq!(solver_part1_solver(&input_generator(input)), expected);
+        }
+        assert_eq!(solver_part2_solver(&input_generator(INPUT)), 45);
+        assert_eq!(solver_part2_solver(&input_generator(INPUT2)), 64);
+    }
+
+    #[test]
+    fn paths() {
+        let maze = Maze::parse(INPUT4).unwrap();
+        let solution = maze.solve(&Costs::PUZZLE).unwrap();
+        let paths = solution.paths();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0][0], (maze.start(), Orientation::East));
+        assert_eq!(paths[0].last().unwrap().0, maze.end());
+        assert_eq!(
+            maze.render(&paths),
+            "\
+##########
+#>>>>>>>E#
+#^##.#####
+#^<#.....#
+##^#####.#
+#S^......#
+##########
+"
+        );
+
+        let maze = Maze::parse(INPUT).unwrap();
+        let solution = maze.solve(&Costs::PUZZLE).unwrap();
+        assert_eq!(solution.paths().len(), 3);
+    }
+
+    #[test]
+    fn costs() {
+        // Turning for free makes it a plain shortest path
+        let maze = Maze::parse(INPUT6).unwrap();
+        let free = Costs {
+            step: 1,
+            turn: 0,
+            reverse: 0,
+        };
+        let solution = maze.solve(&free).unwrap();
+        assert_eq!(solution.cost, 5);
+        assert_eq!(solution.tiles().len(), 12);
+    }
+
+    #[test]
+    fn missing() {
+        assert_eq!(Maze::parse("#.E#").err(), Some(MazeError::MissingStart));
+        assert_eq!(Maze::parse("#S.#").err(), Some(MazeError::MissingEnd));
+        let maze = Maze::parse("S#E").unwrap();
+        assert!(maze.solve(&Costs::PUZZLE).is_none());
+    }
 }
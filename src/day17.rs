@@ -4,12 +4,12 @@ use aoc_runner_derive::aoc_generator;
 type Input = String;
 
 #[aoc_generator(day17)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Registers {
+pub(crate) struct Registers {
     a: u64,
     b: u64,
     c: u64,
@@ -76,7 +76,7 @@ impl Iterator for OutputIter {
         }
     }
 }
-fn parse_input(input: &str) -> (Registers, Vec<u8>) {
+pub(crate) fn parse_input(input: &str) -> (Registers, Vec<u8>) {
     let mut lines = input.lines();
     let reg_a = lines.next().unwrap()[12..].parse::<u64>().unwrap();
     let reg_b = lines.next().unwrap()[12..].parse::<u64>().unwrap();
@@ -195,7 +195,7 @@ fn run_program_shift(reg: &mut Registers, ins: &[u8]) -> Vec<u8> {
     out
 }
 #[aoc(day17, part1)]
-fn solver_part1(input: &Input) -> String {
+pub(crate) fn solver_part1(input: &Input) -> String {
     let (mut reg, ins) = parse_input(input);
 
     let out = run_program(&mut reg, &ins);
@@ -209,7 +209,7 @@ fn solver_part1(input: &Input) -> String {
 }
 
 #[aoc(day17, part1, ITER)]
-fn solver_part1_iter(input: &Input) -> String {
+pub(crate) fn solver_part1_iter(input: &Input) -> String {
     let (reg, ins) = parse_input(input);
     let iter = OutputIter::new(reg, ins);
     let mut string = String::new();
@@ -222,7 +222,7 @@ fn solver_part1_iter(input: &Input) -> String {
 }
 
 #[aoc(day17, part1, SHIFT)]
-fn solver_part1_shift(input: &Input) -> String {
+pub(crate) fn solver_part1_shift(input: &Input) -> String {
     let (mut reg, ins) = parse_input(input);
 
     let out = run_program_shift(&mut reg, &ins);
@@ -236,7 +236,7 @@ fn solver_part1_shift(input: &Input) -> String {
 }
 
 #[aoc(day17, part1, SHIFT_FAST_PARSE)]
-fn solver_part1_shift_fast_parse(input: &Input) -> String {
+pub(crate) fn solver_part1_shift_fast_parse(input: &Input) -> String {
     let (mut reg, ins) = fast_parse_input(input);
 
     let out = run_program_shift(&mut reg, &ins);
@@ -250,7 +250,7 @@ fn solver_part1_shift_fast_parse(input: &Input) -> String {
 }
 
 #[aoc(day17, part1, SHIFT_FASTER_PARSE)]
-fn solver_part1_shift_faster_parse(input: &Input) -> String {
+pub(crate) fn solver_part1_shift_faster_parse(input: &Input) -> String {
     let (mut reg, ins) = faster_parse_input(input);
 
     let out = run_program_shift(&mut reg, &ins);
@@ -309,7 +309,7 @@ fn step_fast(mut reg: Registers, ins: &[u8]) -> u8 {
 }
 
 #[aoc(day17, part2)]
-fn solver_part2(input: &Input) -> u64 {
+pub(crate) fn solver_part2(input: &Input) -> u64 {
     let (_, ins) = faster_parse_input(input);
     for i in 0..512 {
         let reg = Registers::new(i, 0, 0);
@@ -321,7 +321,7 @@ fn solver_part2(input: &Input) -> u64 {
 }
 
 #[aoc(day17, part2, BACKWARDS)]
-fn solver_part2_backwrds(input: &Input) -> u64 {
+pub(crate) fn solver_part2_backwrds(input: &Input) -> u64 {
     let (_, ins) = faster_parse_input(input);
     let mut values = vec![0u64; ins.len()];
     let mut index = ins.len() - 1;
@@ -357,7 +357,7 @@ fn solver_part2_backwrds(input: &Input) -> u64 {
 }
 
 #[aoc(day17, part2, BACKWARDS_FASTER)]
-fn solver_part2_backwrds_faster(input: &Input) -> u64 {
+pub(crate) fn solver_part2_backwrds_faster(input: &Input) -> u64 {
     let ins = faster_parse_ins(input);
     let mut values = vec![0u64; ins.len()];
     let mut index = ins.len() - 1;
@@ -2,11 +2,12 @@ use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
 use aoc_tools::grid::Coord;
 use aoc_tools::grid::Grid;
+use std::collections::VecDeque;
 
 type Input = String;
 
 #[aoc_generator(day18)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -20,7 +21,7 @@ fn find_path(pos: Coord, grid: &Grid<u8>, dist: &mut Grid<u32>, distance: u32) {
 }
 
 #[aoc(day18, part1)]
-fn solver_part1(input: &Input) -> u32 {
+pub(crate) fn solver_part1(input: &Input) -> u32 {
     let size = 71;
     let bytes = 1024;
     let mut grid = Grid::new(0u8, size, size);
@@ -40,7 +41,7 @@ fn solver_part1(input: &Input) -> u32 {
 }
 
 #[aoc(day18, part2)]
-fn solver_part2(input: &Input) -> String {
+pub(crate) fn solver_part2(input: &Input) -> String {
     let size = 71;
     let mut bytes = 1024;
     let mut grid = Grid::new(0u8, size, size);
@@ -66,11 +67,193 @@ fn solver_part2(input: &Input) -> String {
     }
 }
 
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        match self.rank[a].cmp(&self.rank[b]) {
+            std::cmp::Ordering::Less => self.parent[a] = b,
+            std::cmp::Ordering::Greater => self.parent[b] = a,
+            std::cmp::Ordering::Equal => {
+                self.parent[b] = a;
+                self.rank[a] += 1;
+            }
+        }
+    }
+}
+
+// A `size` x `size` memory space with bytes falling in order, walked from the top left to the bottom right
+pub struct Memory {
+    size: usize,
+    bytes: Vec<Coord>,
+}
+
+impl Memory {
+    pub fn new(size: usize, bytes: Vec<Coord>) -> Self {
+        Memory { size, bytes }
+    }
+
+    pub fn parse(input: &str, size: usize) -> Self {
+        let bytes = input
+            .lines()
+            .map(|line| {
+                let (x, y) = line.split_once(',').unwrap();
+                Coord::new(x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        Memory::new(size, bytes)
+    }
+
+    pub fn bytes(&self) -> &[Coord] {
+        &self.bytes
+    }
+
+    fn corrupted(&self, fallen: usize) -> Grid<bool> {
+        let mut grid = Grid::new(false, self.size, self.size);
+        for &byte in &self.bytes[..fallen] {
+            grid[byte] = true;
+        }
+        grid
+    }
+
+    // Fewest steps to the exit once `fallen` bytes have landed
+    pub fn shortest_path(&self, fallen: usize) -> Option<u32> {
+        let corrupted = self.corrupted(fallen);
+        let start = Coord::new(0, 0);
+        let end = Coord::new(self.size as i32 - 1, self.size as i32 - 1);
+        if corrupted[start] {
+            return None;
+        }
+        let mut dist = Grid::new(u32::MAX, self.size, self.size);
+        let mut queue = VecDeque::from([start]);
+        dist[start] = 0;
+        while let Some(pos) = queue.pop_front() {
+            if pos == end {
+                return Some(dist[pos]);
+            }
+            for (next, &blocked) in corrupted.orthogs_coords(&pos).into_iter().flatten() {
+                if !blocked && dist[next] == u32::MAX {
+                    dist[next] = dist[pos] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    // Index and position of the first of the first `fallen` bytes to cut off the exit.
+    // Starts with them all down and lifts them in reverse until the corners rejoin
+    pub fn first_blocking(&self, fallen: usize) -> Option<(usize, Coord)> {
+        let index = |c: Coord| c.y as usize * self.size + c.x as usize;
+        let mut landed = vec![usize::MAX; self.size * self.size];
+        for (i, &byte) in self.bytes[..fallen].iter().enumerate() {
+            landed[index(byte)] = landed[index(byte)].min(i);
+        }
+        let corrupted = self.corrupted(fallen);
+        let mut sets = UnionFind::new(self.size * self.size);
+        for (pos, _) in corrupted.iter().filter(|(_, &blocked)| !blocked) {
+            for (next, _) in corrupted.orthogs_coords(&pos).into_iter().flatten() {
+                if !corrupted[next] {
+                    sets.union(index(pos), index(next));
+                }
+            }
+        }
+
+        let start = Coord::new(0, 0);
+        let end = Coord::new(self.size as i32 - 1, self.size as i32 - 1);
+        let mut free = corrupted;
+        for c in free.coord_iter() {
+            free[c] = !free[c];
+        }
+        if free[start] && free[end] && sets.find(index(start)) == sets.find(index(end)) {
+            return None;
+        }
+        for (i, &byte) in self.bytes[..fallen].iter().enumerate().rev() {
+            // Only the first byte to land on a cell corrupts it
+            if landed[index(byte)] != i {
+                continue;
+            }
+            free[byte] = true;
+            for (next, &open) in free.orthogs_coords(&byte).into_iter().flatten() {
+                if open {
+                    sets.union(index(byte), index(next));
+                }
+            }
+            if free[start] && free[end] && sets.find(index(start)) == sets.find(index(end)) {
+                return Some((i, byte));
+            }
+        }
+        None
+    }
+
+    // Same answer as `first_blocking`, found by binary searching on `shortest_path`
+    pub fn first_blocking_search(&self, fallen: usize) -> Option<(usize, Coord)> {
+        self.shortest_path(0)?;
+        if self.shortest_path(fallen).is_some() {
+            return None;
+        }
+        // Invariant: open with `low` bytes down, blocked with `high`
+        let (mut low, mut high) = (0, fallen);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if self.shortest_path(mid).is_some() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Some((high - 1, self.bytes[high - 1]))
+    }
+}
+
+#[aoc(day18, part1, BFS)]
+pub(crate) fn solver_part1_bfs(input: &Input) -> u32 {
+    Memory::parse(input, 71).shortest_path(1024).unwrap()
+}
+
+#[aoc(day18, part2, UNION_FIND)]
+pub(crate) fn solver_part2_union_find(input: &Input) -> String {
+    let memory = Memory::parse(input, 71);
+    let (_, byte) = memory.first_blocking(memory.bytes().len()).unwrap();
+    format!("{},{}", byte.x, byte.y)
+}
+
+#[aoc(day18, part2, BINARY_SEARCH)]
+pub(crate) fn solver_part2_binary_search(input: &Input) -> String {
+    let memory = Memory::parse(input, 71);
+    let (_, byte) = memory.first_blocking_search(memory.bytes().len()).unwrap();
+    format!("{},{}", byte.x, byte.y)
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
     use super::solver_part2;
+    use super::Memory;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "5,4
 4,2
@@ -107,4 +290,38 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), "")
     }
+
+    #[test]
+    fn memory() {
+        let memory = Memory::parse(INPUT, 7);
+        assert_eq!(memory.shortest_path(12), Some(22));
+        let first = Some((20, Coord::new(6, 1)));
+        assert_eq!(memory.first_blocking(memory.bytes().len()), first);
+        assert_eq!(memory.first_blocking_search(memory.bytes().len()), first);
+        assert_eq!(memory.first_blocking(20), None);
+        assert_eq!(memory.first_blocking_search(20), None);
+    }
+
+    #[test]
+    fn large() {
+        // A wall down the middle with its gap left for last, after a few bytes that block nothing
+        let size = 501;
+        let mut bytes = vec![
+            Coord::new(100, 100),
+            Coord::new(400, 37),
+            Coord::new(10, 490),
+            Coord::new(499, 3),
+        ];
+        bytes.extend((0..size - 1).map(|y| Coord::new(250, y)));
+        bytes.push(Coord::new(250, size - 1));
+        let memory = Memory::new(size as usize, bytes);
+        let fallen = memory.bytes().len();
+        let expected = Some((fallen - 1, Coord::new(250, size - 1)));
+        assert_eq!(memory.first_blocking(fallen), expected);
+        assert_eq!(memory.first_blocking_search(fallen), expected);
+        assert_eq!(
+            memory.shortest_path(fallen - 1),
+            Some(2 * (size as u32 - 1))
+        );
+    }
 }
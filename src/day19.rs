@@ -16,7 +16,7 @@ use rayon::slice::ParallelSlice;
 type Input = String;
 
 #[aoc_generator(day19)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -187,7 +187,7 @@ fn possible_pattern_count_scc<'a>(
     count
 }
 #[aoc(day19, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut lines = input.lines();
     let towels = lines
         .next()
@@ -203,7 +203,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day19, part1, PARALLEL)]
-fn solver_part1_parallel(input: &Input) -> usize {
+pub(crate) fn solver_part1_parallel(input: &Input) -> usize {
     let mut lines = input.lines();
     let towels = lines
         .next()
@@ -220,7 +220,7 @@ fn solver_part1_parallel(input: &Input) -> usize {
 }
 
 #[aoc(day19, part1, PARALLEL_DASH)]
-fn solver_part1_parallel_dash(input: &Input) -> usize {
+pub(crate) fn solver_part1_parallel_dash(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = DashMap::new();
     let towels = lines
@@ -238,7 +238,7 @@ fn solver_part1_parallel_dash(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let mut lines = input.lines();
     let mut cache = HashMap::new();
     let towels = lines
@@ -255,7 +255,7 @@ fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL)]
-fn solver_part2_parallel(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = HashMap::new();
     let towels = lines
@@ -273,7 +273,7 @@ fn solver_part2_parallel(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL_DASH)]
-fn solver_part2_parallel_dash(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_dash(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = DashMap::new();
     let towels = lines
@@ -291,7 +291,7 @@ fn solver_part2_parallel_dash(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL_SCC)]
-fn solver_part2_parallel_scc(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_scc(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = scc::HashMap::new();
     let towels = lines
@@ -309,7 +309,7 @@ fn solver_part2_parallel_scc(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL_WITH)]
-fn solver_part2_parallel_with(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_with(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = HashMap::new();
     let towels = lines
@@ -329,7 +329,7 @@ fn solver_part2_parallel_with(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL_CHUNKS)]
-fn solver_part2_parallel_chunks(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_chunks(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = HashMap::new();
     let towels = lines
@@ -352,7 +352,7 @@ fn solver_part2_parallel_chunks(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL_WITH_FX)]
-fn solver_part2_parallel_with_fx(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_with_fx(input: &Input) -> usize {
     let mut lines = input.lines();
     let cache = FxHashMap::default();
     let towels = lines
@@ -372,7 +372,7 @@ fn solver_part2_parallel_with_fx(input: &Input) -> usize {
 }
 
 #[aoc(day19, part2, PARALLEL_MANUAL)]
-fn solver_part2_parallel_manual(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_manual(input: &Input) -> usize {
     let mut lines = input.lines();
 
     let towels = lines
@@ -407,11 +407,197 @@ fn solver_part2_parallel_manual(input: &Input) -> usize {
         })
         .sum()
 }
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    towel: Option<usize>,
+}
+
+// Trie over the towel patterns, walked from each position of a design
+#[derive(Debug)]
+pub struct TowelIndex {
+    towels: Vec<String>,
+    nodes: Vec<TrieNode>,
+}
+
+impl TowelIndex {
+    pub fn new<'a>(towels: impl IntoIterator<Item = &'a str>) -> TowelIndex {
+        let mut index = TowelIndex {
+            towels: Vec::new(),
+            nodes: vec![TrieNode::default()],
+        };
+        for towel in towels.into_iter().filter(|t| !t.is_empty()) {
+            let mut node = 0;
+            for b in towel.bytes() {
+                node = match index.child(node, b) {
+                    Some(next) => next,
+                    None => {
+                        index.nodes.push(TrieNode::default());
+                        let next = index.nodes.len() - 1;
+                        index.nodes[node].children.push((b, next));
+                        next
+                    }
+                };
+            }
+            if index.nodes[node].towel.is_none() {
+                index.nodes[node].towel = Some(index.towels.len());
+                index.towels.push(towel.to_string());
+            }
+        }
+        index
+    }
+
+    pub fn parse(input: &str) -> (TowelIndex, Vec<&str>) {
+        let mut lines = input.lines();
+        let index = TowelIndex::new(lines.next().unwrap().split(", "));
+        lines.next();
+        (index, lines.collect())
+    }
+
+    pub fn towels(&self) -> &[String] {
+        &self.towels
+    }
+
+    fn child(&self, node: usize, b: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(c, _)| *c == b)
+            .map(|(_, next)| *next)
+    }
+
+    // Every towel matching the design at `start`, as (towel, end), shortest first
+    fn matches<'a>(
+        &'a self,
+        design: &'a [u8],
+        start: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut node = 0;
+        let mut pos = start;
+        std::iter::from_fn(move || {
+            while pos < design.len() {
+                node = self.child(node, design[pos])?;
+                pos += 1;
+                if let Some(towel) = self.nodes[node].towel {
+                    return Some((towel, pos));
+                }
+            }
+            None
+        })
+    }
+
+    // possible[i] is true when design[i..] can be made
+    fn suffix_possible(&self, design: &[u8]) -> Vec<bool> {
+        let mut possible = vec![false; design.len() + 1];
+        possible[design.len()] = true;
+        for i in (0..design.len()).rev() {
+            possible[i] = self.matches(design, i).any(|(_, end)| possible[end]);
+        }
+        possible
+    }
+
+    pub fn is_possible(&self, design: &str) -> bool {
+        self.suffix_possible(design.as_bytes())[0]
+    }
+
+    // Number of arrangements, or None if it does not fit in a u128
+    pub fn count(&self, design: &str) -> Option<u128> {
+        let design = design.as_bytes();
+        let mut counts = vec![0u128; design.len() + 1];
+        counts[design.len()] = 1;
+        for i in (0..design.len()).rev() {
+            counts[i] = self
+                .matches(design, i)
+                .try_fold(0u128, |sum, (_, end)| sum.checked_add(counts[end]))?;
+        }
+        Some(counts[0])
+    }
+
+    // Towels that appear in no arrangement of any of the designs
+    pub fn unused_towels<'a>(&self, designs: impl IntoIterator<Item = &'a str>) -> Vec<&str> {
+        let mut used = vec![false; self.towels.len()];
+        for design in designs {
+            let design = design.as_bytes();
+            let possible = self.suffix_possible(design);
+            let mut reachable = vec![false; design.len() + 1];
+            reachable[0] = possible[0];
+            for i in 0..design.len() {
+                if reachable[i] {
+                    for (towel, end) in self.matches(design, i) {
+                        if possible[end] {
+                            used[towel] = true;
+                            reachable[end] = true;
+                        }
+                    }
+                }
+            }
+        }
+        self.towels
+            .iter()
+            .zip(used)
+            .filter(|(_, used)| !used)
+            .map(|(towel, _)| towel.as_str())
+            .collect()
+    }
+
+    // The first `limit` arrangements, taking shorter towels first
+    pub fn arrangements(&self, design: &str, limit: usize) -> Vec<Vec<&str>> {
+        let design = design.as_bytes();
+        let possible = self.suffix_possible(design);
+        let mut found = Vec::new();
+        if possible[0] {
+            self.arrange(design, 0, &possible, &mut Vec::new(), &mut found, limit);
+        }
+        found
+    }
+
+    fn arrange<'a>(
+        &'a self,
+        design: &[u8],
+        start: usize,
+        possible: &[bool],
+        current: &mut Vec<&'a str>,
+        found: &mut Vec<Vec<&'a str>>,
+        limit: usize,
+    ) {
+        if start == design.len() {
+            found.push(current.clone());
+            return;
+        }
+        for (towel, end) in self.matches(design, start) {
+            if found.len() >= limit {
+                return;
+            }
+            if possible[end] {
+                current.push(&self.towels[towel]);
+                self.arrange(design, end, possible, current, found, limit);
+                current.pop();
+            }
+        }
+    }
+}
+
+#[aoc(day19, part1, TRIE)]
+pub(crate) fn solver_part1_trie(input: &Input) -> usize {
+    let (index, designs) = TowelIndex::parse(input);
+    designs.iter().filter(|d| index.is_possible(d)).count()
+}
+
+#[aoc(day19, part2, TRIE)]
+pub(crate) fn solver_part2_trie(input: &Input) -> u128 {
+    let (index, designs) = TowelIndex::parse(input);
+    designs.iter().map(|d| index.count(d).unwrap()).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_trie;
     use super::solver_part2;
+    use super::solver_part2_trie;
+    use super::TowelIndex;
 
     static INPUT: &str = "r, wr, b, g, bwu, rb, gb, br
 
@@ -433,4 +619,35 @@ bbrgwb";
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 16)
     }
+
+    #[test]
+    fn part1_trie() {
+        assert_eq!(solver_part1_trie(&input_generator(INPUT)), 6)
+    }
+
+    #[test]
+    fn part2_trie() {
+        assert_eq!(solver_part2_trie(&input_generator(INPUT)), 16)
+    }
+
+    #[test]
+    fn arrangements() {
+        let (index, designs) = TowelIndex::parse(INPUT);
+        assert_eq!(
+            index.arrangements("brwrr", 10),
+            vec![vec!["b", "r", "wr", "r"], vec!["br", "wr", "r"]]
+        );
+        assert_eq!(index.arrangements("brwrr", 1).len(), 1);
+        assert!(index.arrangements("ubwu", 10).is_empty());
+        assert_eq!(index.unused_towels(designs), Vec::<&str>::new());
+        assert_eq!(index.unused_towels(["brwrr"]), vec!["g", "bwu", "rb", "gb"]);
+    }
+
+    #[test]
+    fn overflow() {
+        let index = TowelIndex::new(["a", "aa"]);
+        assert_eq!(index.count(&"a".repeat(100)), Some(573147844013817084101));
+        assert_eq!(index.count(&"a".repeat(200)), None);
+        assert!(index.is_possible(&"a".repeat(200)));
+    }
 }
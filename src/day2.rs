@@ -1,15 +1,16 @@
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
 use itertools::Itertools;
+use std::ops::RangeInclusive;
 
 type Input = String;
 
 #[aoc_generator(day2)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
-fn parse(input: &str) -> Vec<Vec<i32>> {
+pub(crate) fn parse(input: &str) -> Vec<Vec<i32>> {
     input
         .lines()
         .map(|l| {
@@ -57,7 +58,7 @@ fn line2vec(l: &str) -> Vec<i16> {
 }
 
 #[aoc(day2, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let input = parse(input);
     input
         .iter()
@@ -72,7 +73,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day2, part1, FAST)]
-fn solver_part1_fast(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast(input: &Input) -> usize {
     let input = parse(input);
     input
         .iter()
@@ -88,7 +89,7 @@ fn solver_part1_fast(input: &Input) -> usize {
 }
 
 #[aoc(day2, part1, FAST_PARSE)]
-fn solver_part1_fast_parse(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_parse(input: &Input) -> usize {
     let input = parse_fast(input);
     input
         .iter()
@@ -104,7 +105,7 @@ fn solver_part1_fast_parse(input: &Input) -> usize {
 }
 
 #[aoc(day2, part1, FAST_PARSE_CUSTOM)]
-fn solver_part1_fast_parse_custom(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_parse_custom(input: &Input) -> usize {
     let input = parse_custom(input);
     input
         .iter()
@@ -120,7 +121,7 @@ fn solver_part1_fast_parse_custom(input: &Input) -> usize {
 }
 
 #[aoc(day2, part1, FAST_INLINE)]
-fn solver_part1_fast_inline(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_inline(input: &Input) -> usize {
     input
         .lines()
         .filter(|l| {
@@ -134,7 +135,7 @@ fn solver_part1_fast_inline(input: &Input) -> usize {
 }
 
 #[aoc(day2, part1, FAST_INLINE_2)]
-fn solver_part1_fast_inline_2(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_inline_2(input: &Input) -> usize {
     input
         .lines()
         .filter(|l| {
@@ -148,7 +149,7 @@ fn solver_part1_fast_inline_2(input: &Input) -> usize {
 }
 
 #[aoc(day2, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let input = parse(input);
     input
         .iter()
@@ -170,7 +171,7 @@ fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day2, part2, FAST)]
-fn solver_part2_fast(input: &Input) -> usize {
+pub(crate) fn solver_part2_fast(input: &Input) -> usize {
     let input = parse(input);
     input
         .iter()
@@ -188,13 +189,200 @@ fn solver_part2_fast(input: &Input) -> usize {
         .count()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    TrendFlipped,
+    OutOfRange,
+}
+
+// The first bad step, `index` being the level it lands on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Failure {
+    pub index: usize,
+    pub step: i32,
+    pub violation: Violation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    pub failure: Option<Failure>,
+    // The smallest sets of level indices whose removal makes the report safe
+    pub fixes: Vec<Vec<usize>>,
+}
+
+pub struct ReportAnalyser {
+    steps: RangeInclusive<i32>,
+    max_removals: usize,
+}
+
+impl ReportAnalyser {
+    pub fn new() -> Self {
+        ReportAnalyser {
+            steps: 1..=3,
+            max_removals: 1,
+        }
+    }
+
+    // Bounds on the size of each step, regardless of direction
+    pub fn with_steps(mut self, steps: RangeInclusive<i32>) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    pub fn with_max_removals(mut self, max_removals: usize) -> Self {
+        self.max_removals = max_removals;
+        self
+    }
+
+    // The trend is set by the first non-zero step. A step against it counts as a flip even if
+    // its size is also out of range
+    pub fn check(&self, report: &[i32]) -> Option<Failure> {
+        let mut trend = 0;
+        for (index, (a, b)) in report.iter().tuple_windows().enumerate() {
+            let step = b - a;
+            let violation = if trend != 0 && step.signum() == -trend {
+                Some(Violation::TrendFlipped)
+            } else if !self.steps.contains(&step.abs()) {
+                Some(Violation::OutOfRange)
+            } else {
+                None
+            };
+            if let Some(violation) = violation {
+                return Some(Failure {
+                    index: index + 1,
+                    step,
+                    violation,
+                });
+            }
+            if trend == 0 {
+                trend = step.signum();
+            }
+        }
+        None
+    }
+
+    fn check_without(&self, report: &[i32], removed: &[usize]) -> Option<Failure> {
+        let kept = report
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .map(|(_, &level)| level)
+            .collect_vec();
+        self.check(&kept)
+    }
+
+    pub fn analyse(&self, report: &[i32]) -> Diagnosis {
+        let failure = self.check(report);
+        let mut fixes = Vec::new();
+        if failure.is_some() {
+            for count in 1..=self.max_removals.min(report.len()) {
+                fixes = (0..report.len())
+                    .combinations(count)
+                    .filter(|removed| self.check_without(report, removed).is_none())
+                    .collect();
+                if !fixes.is_empty() {
+                    break;
+                }
+            }
+        }
+        Diagnosis { failure, fixes }
+    }
+
+    // Safe as it is or after removing at most the allowed number of levels
+    pub fn is_safe(&self, report: &[i32]) -> bool {
+        let diagnosis = self.analyse(report);
+        diagnosis.failure.is_none() || !diagnosis.fixes.is_empty()
+    }
+}
+
+impl Default for ReportAnalyser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[aoc(day2, part1, ANALYSER)]
+pub(crate) fn solver_part1_analyser(input: &Input) -> usize {
+    let analyser = ReportAnalyser::new().with_max_removals(0);
+    parse(input).iter().filter(|r| analyser.is_safe(r)).count()
+}
+
+#[aoc(day2, part2, ANALYSER)]
+pub(crate) fn solver_part2_analyser(input: &Input) -> usize {
+    let analyser = ReportAnalyser::new();
+    parse(input).iter().filter(|r| analyser.is_safe(r)).count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
+    use super::parse;
     use super::solver_part1;
+    use super::solver_part1_analyser;
+    use super::solver_part2_analyser;
+    use super::Failure;
+    use super::ReportAnalyser;
+    use super::Violation;
+
+    static INPUT: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
 
     #[test]
     fn sample1() {
         assert_eq!(solver_part1(&input_generator("input")), 0)
     }
+
+    #[test]
+    fn analyser() {
+        assert_eq!(solver_part1_analyser(&input_generator(INPUT)), 2);
+        assert_eq!(solver_part2_analyser(&input_generator(INPUT)), 4);
+
+        let analyser = ReportAnalyser::new();
+        let diagnoses = parse(INPUT)
+            .iter()
+            .map(|r| analyser.analyse(r))
+            .collect::<Vec<_>>();
+        let failure = |index, step, violation| {
+            Some(Failure {
+                index,
+                step,
+                violation,
+            })
+        };
+        assert_eq!(diagnoses[0].failure, None);
+        assert_eq!(diagnoses[1].failure, failure(2, 5, Violation::OutOfRange));
+        assert!(diagnoses[1].fixes.is_empty());
+        assert_eq!(diagnoses[2].failure, failure(3, -4, Violation::OutOfRange));
+        assert_eq!(
+            diagnoses[3].failure,
+            failure(2, -1, Violation::TrendFlipped)
+        );
+        assert_eq!(diagnoses[3].fixes, [[1], [2]]);
+        assert_eq!(diagnoses[4].failure, failure(3, 0, Violation::OutOfRange));
+        assert_eq!(diagnoses[4].fixes, [[2], [3]]);
+    }
+
+    #[test]
+    fn removals() {
+        let report = [1, 9, 2, 3, 8, 4];
+        let analyser = ReportAnalyser::new();
+        assert!(!analyser.is_safe(&report));
+        let diagnosis = analyser.with_max_removals(2).analyse(&report);
+        assert_eq!(diagnosis.fixes, [[1, 4]]);
+
+        let analyser = ReportAnalyser::new().with_steps(0..=10);
+        assert_eq!(analyser.check(&[1, 9, 9, 19]), None);
+        assert_eq!(
+            analyser.check(&[5, 5, 6, 4]),
+            Some(Failure {
+                index: 3,
+                step: -2,
+                violation: Violation::TrendFlipped
+            })
+        );
+    }
 }
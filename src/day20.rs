@@ -4,6 +4,7 @@ use aoc_tools::grid::Coord;
 use aoc_tools::grid::Grid;
 use itertools::Itertools;
 use rayon::prelude::*;
+use std::collections::BTreeMap;
 
 type Input = String;
 
@@ -324,7 +325,7 @@ fn find_shortcuts_2_slice(_steps: &Grid<u32>, path: &[Coord]) -> usize {
 }
 
 #[aoc(day20, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -346,7 +347,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day20, part1, FASTER)]
-fn solver_part1_faster(input: &Input) -> usize {
+pub(crate) fn solver_part1_faster(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -364,7 +365,7 @@ fn solver_part1_faster(input: &Input) -> usize {
 }
 
 #[aoc(day20, part1, I16)]
-fn solver_part1_i16(input: &Input) -> usize {
+pub(crate) fn solver_part1_i16(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -382,7 +383,7 @@ fn solver_part1_i16(input: &Input) -> usize {
 }
 
 #[aoc(day20, part1, ALT)]
-fn solver_part1_alt(input: &Input) -> usize {
+pub(crate) fn solver_part1_alt(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -427,7 +428,7 @@ pub fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day20, part2, PARALLEL)]
-fn solver_part2_parallel(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -445,7 +446,7 @@ fn solver_part2_parallel(input: &Input) -> usize {
 }
 
 #[aoc(day20, part2, PARALLEL_3)]
-fn solver_part2_parallel_3(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_3(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -481,7 +482,7 @@ pub fn solver_part2_parallel_4(input: &Input) -> usize {
 }
 
 #[aoc(day20, part2, PARALLEL_2)]
-fn solver_part2_parallel_2(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel_2(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -499,7 +500,7 @@ fn solver_part2_parallel_2(input: &Input) -> usize {
 }
 
 #[aoc(day20, part2, FULL_SLICE)]
-fn solver_part2_full_slice(input: &Input) -> usize {
+pub(crate) fn solver_part2_full_slice(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -517,7 +518,7 @@ fn solver_part2_full_slice(input: &Input) -> usize {
 }
 
 #[aoc(day20, part2, WORSE)]
-fn solver_part2_worse(input: &Input) -> usize {
+pub(crate) fn solver_part2_worse(input: &Input) -> usize {
     let mut grid = parse_input(input);
     let mut start = Coord::new(0, 0);
     for c in grid.coord_iter() {
@@ -550,6 +551,105 @@ pub fn solver_part2_slice(input: &Input) -> usize {
     find_shortcuts_2_slice(&steps, &path)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cheat {
+    pub start: Coord,
+    pub end: Coord,
+    pub saving: usize,
+}
+
+// All cheats along the single track from S to E
+pub struct CheatAnalyser {
+    path: Vec<Coord>,
+}
+
+impl CheatAnalyser {
+    // Panics if the track has no 'S' or 'E'
+    pub fn new(input: &str) -> CheatAnalyser {
+        let mut grid = parse_input(input);
+        let (mut start, mut end) = (None, None);
+        for c in grid.coord_iter() {
+            if grid[c] == b'S' {
+                start = Some(c);
+                grid[c] = b'.';
+            }
+            if grid[c] == b'E' {
+                end = Some(c);
+                grid[c] = b'.';
+            }
+        }
+        let start = start.expect("Race track has no start 'S'");
+        end.expect("Race track has no end 'E'");
+        CheatAnalyser {
+            path: find_path_i16(&grid, start),
+        }
+    }
+
+    // Picoseconds to finish without cheating
+    pub fn track_length(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    // Calls `f` with every cheat of at most `max_duration` that saves at least `min_saving`
+    fn for_each_cheat(&self, max_duration: usize, min_saving: usize, mut f: impl FnMut(Cheat)) {
+        let min_saving = min_saving.max(1);
+        for (i, start) in self.path.iter().enumerate() {
+            for (j, end) in self.path.iter().enumerate().skip(i + min_saving + 1) {
+                let duration = start.rectilinear_distance(end) as usize;
+                if duration <= max_duration && j - i - duration >= min_saving {
+                    f(Cheat {
+                        start: *start,
+                        end: *end,
+                        saving: j - i - duration,
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn count(&self, max_duration: usize, min_saving: usize) -> usize {
+        let mut count = 0;
+        self.for_each_cheat(max_duration, min_saving, |_| count += 1);
+        count
+    }
+
+    // Number of cheats for each saving
+    pub fn histogram(&self, max_duration: usize, min_saving: usize) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        self.for_each_cheat(max_duration, min_saving, |cheat| {
+            *histogram.entry(cheat.saving).or_insert(0) += 1
+        });
+        histogram
+    }
+
+    // The `n` cheats saving the most time, largest first
+    pub fn best(&self, max_duration: usize, min_saving: usize, n: usize) -> Vec<Cheat> {
+        let mut cheats = Vec::new();
+        self.for_each_cheat(max_duration, min_saving, |cheat| cheats.push(cheat));
+        cheats.sort_by_key(|c| {
+            (
+                std::cmp::Reverse(c.saving),
+                c.start.y,
+                c.start.x,
+                c.end.y,
+                c.end.x,
+            )
+        });
+        cheats.truncate(n);
+        cheats
+    }
+}
+
+#[aoc(day20, part1, ANALYSER)]
+pub(crate) fn solver_part1_analyser(input: &Input) -> usize {
+    CheatAnalyser::new(input).count(2, 100)
+}
+
+#[aoc(day20, part2, ANALYSER)]
+pub(crate) fn solver_part2_analyser(input: &Input) -> usize {
+    CheatAnalyser::new(input).count(20, 100)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day20::solver_part1_faster;
@@ -557,6 +657,9 @@ mod tests {
     use super::input_generator;
     use super::solver_part1;
     use super::solver_part2;
+    use super::Cheat;
+    use super::CheatAnalyser;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "###############
 #...#...#.....#
@@ -588,4 +691,71 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 0)
     }
+
+    #[test]
+    fn histogram_2() {
+        let analyser = CheatAnalyser::new(INPUT);
+        assert_eq!(analyser.track_length(), 84);
+        assert_eq!(
+            analyser.histogram(2, 1).into_iter().collect::<Vec<_>>(),
+            vec![
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1)
+            ]
+        );
+        assert_eq!(
+            analyser.best(2, 1, 1),
+            vec![Cheat {
+                start: Coord::new(7, 7),
+                end: Coord::new(5, 7),
+                saving: 64
+            }]
+        );
+    }
+
+    #[test]
+    fn histogram_20() {
+        let analyser = CheatAnalyser::new(INPUT);
+        assert_eq!(
+            analyser.histogram(20, 50).into_iter().collect::<Vec<_>>(),
+            vec![
+                (50, 32),
+                (52, 31),
+                (54, 29),
+                (56, 39),
+                (58, 25),
+                (60, 23),
+                (62, 20),
+                (64, 19),
+                (66, 12),
+                (68, 14),
+                (70, 12),
+                (72, 22),
+                (74, 4),
+                (76, 3)
+            ]
+        );
+        assert_eq!(analyser.count(20, 76), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Race track has no start 'S'")]
+    fn missing_start() {
+        CheatAnalyser::new("#####\n#..E#\n#####");
+    }
+
+    #[test]
+    #[should_panic(expected = "Race track has no end 'E'")]
+    fn missing_end() {
+        CheatAnalyser::new("#####\n#S..#\n#####");
+    }
 }
@@ -165,7 +165,7 @@ impl From<&Key> for (i8, i8) {
 }
 
 #[aoc_generator(day21)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -411,7 +411,7 @@ fn find_shortest_flat_cached<const N: usize>(
 }
 
 #[aoc(day21, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut state = [Button::A; 2];
     input
         .lines()
@@ -434,7 +434,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day21, part1, FASTER)]
-fn solver_part1_faster(input: &Input) -> usize {
+pub(crate) fn solver_part1_faster(input: &Input) -> usize {
     input
         .lines()
         .map(|line| {
@@ -455,7 +455,7 @@ fn solver_part1_faster(input: &Input) -> usize {
 }
 
 #[aoc(day21, part1, Cache)]
-fn solver_part1_cache(input: &Input) -> usize {
+pub(crate) fn solver_part1_cache(input: &Input) -> usize {
     const SIZE: usize = 2;
     input
         .lines()
@@ -481,7 +481,7 @@ fn solver_part1_cache(input: &Input) -> usize {
 }
 
 #[aoc(day21, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     const SIZE: usize = 25;
     input
         .lines()
@@ -503,7 +503,7 @@ fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day21, part2, GRID_CACHE)]
-fn solver_part2_grid_cache(input: &Input) -> usize {
+pub(crate) fn solver_part2_grid_cache(input: &Input) -> usize {
     const SIZE: usize = 25;
     input
         .lines()
@@ -529,7 +529,7 @@ fn solver_part2_grid_cache(input: &Input) -> usize {
 }
 
 #[aoc(day21, part2, FLAT_CACHE)]
-fn solver_part2_flat_cache(input: &Input) -> usize {
+pub(crate) fn solver_part2_flat_cache(input: &Input) -> usize {
     const SIZE: usize = 25;
     input
         .lines()
@@ -554,12 +554,247 @@ fn solver_part2_flat_cache(input: &Input) -> usize {
         .sum()
 }
 
+pub const NUMERIC_KEYPAD: &str = "789
+456
+123
+#0A";
+
+pub const DIRECTIONAL_KEYPAD: &str = "#^A
+<v>";
+
+// Keypad described by a character grid, with `gap` marking the cell the arm may not enter
+#[derive(Debug, Clone)]
+pub struct Keypad {
+    keys: Vec<(u8, (i8, i8))>,
+    gap: Option<(i8, i8)>,
+    // moves[from][to] holds every candidate press sequence, each ending in `A`
+    moves: Vec<Vec<Vec<Vec<u8>>>>,
+}
+
+impl Keypad {
+    pub fn new(layout: &str, gap: u8) -> Keypad {
+        let mut keys = Vec::new();
+        let mut gap_pos = None;
+        for (y, line) in layout.lines().enumerate() {
+            for (x, b) in line.bytes().enumerate() {
+                let pos = (x as i8, y as i8);
+                if b == gap {
+                    gap_pos = Some(pos);
+                } else {
+                    keys.push((b, pos));
+                }
+            }
+        }
+        let mut keypad = Keypad {
+            keys,
+            gap: gap_pos,
+            moves: Vec::new(),
+        };
+        keypad.moves = keypad
+            .keys
+            .iter()
+            .map(|&(_, from)| {
+                keypad
+                    .keys
+                    .iter()
+                    .map(|&(_, to)| keypad.paths(from, to))
+                    .collect()
+            })
+            .collect();
+        keypad
+    }
+
+    pub fn numeric() -> Keypad {
+        Keypad::new(NUMERIC_KEYPAD, b'#')
+    }
+
+    pub fn directional() -> Keypad {
+        Keypad::new(DIRECTIONAL_KEYPAD, b'#')
+    }
+
+    fn index(&self, key: u8) -> usize {
+        self.keys
+            .iter()
+            .position(|&(k, _)| k == key)
+            .unwrap_or_else(|| panic!("No key {} on keypad", key as char))
+    }
+
+    fn position(&self, key: u8) -> Option<(i8, i8)> {
+        self.keys.iter().find(|&&(k, _)| k == key).map(|&(_, p)| p)
+    }
+
+    // Only the two L shaped routes can be optimal, zig-zagging always costs more presses
+    fn paths(&self, from: (i8, i8), to: (i8, i8)) -> Vec<Vec<u8>> {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let x_moves = vec![if dx < 0 { b'<' } else { b'>' }; dx.unsigned_abs() as usize];
+        let y_moves = vec![if dy < 0 { b'^' } else { b'v' }; dy.unsigned_abs() as usize];
+        let mut paths = vec![
+            [x_moves.as_slice(), &y_moves, b"A"].concat(),
+            [y_moves.as_slice(), &x_moves, b"A"].concat(),
+        ];
+        paths.dedup();
+        paths.retain(|path| self.walk(from, path).is_some());
+        paths
+    }
+
+    // Follow the presses from `from`, returning the keys pressed with `A`
+    fn walk(&self, mut pos: (i8, i8), presses: &[u8]) -> Option<Vec<u8>> {
+        let mut pressed = Vec::new();
+        for &p in presses {
+            match p {
+                b'<' => pos.0 -= 1,
+                b'>' => pos.0 += 1,
+                b'^' => pos.1 -= 1,
+                b'v' => pos.1 += 1,
+                _ => pressed.push(self.keys.iter().find(|&&(_, k)| k == pos)?.0),
+            }
+            if Some(pos) == self.gap || !self.keys.iter().any(|&(_, k)| k == pos) {
+                return None;
+            }
+        }
+        Some(pressed)
+    }
+
+    // Keys pressed on this keypad by a robot receiving `presses`, starting at `A`
+    pub fn decode(&self, presses: &[u8]) -> Option<Vec<u8>> {
+        self.walk(self.position(b'A')?, presses)
+    }
+}
+
+// A door keypad operated through `depth` robot held directional keypads
+pub struct KeypadChain {
+    door: Keypad,
+    remote: Keypad,
+    depth: usize,
+    // costs[level][from][to] presses needed at the top to press `to` after `from`
+    costs: Vec<Vec<Vec<usize>>>,
+    // choice[level][from][to] index of the cheapest move in remote.moves
+    choice: Vec<Vec<Vec<usize>>>,
+}
+
+impl KeypadChain {
+    pub fn new(door: Keypad, remote: Keypad, depth: usize) -> KeypadChain {
+        let n = remote.keys.len();
+        let mut costs = vec![vec![vec![1; n]; n]];
+        let mut choice = vec![vec![vec![0; n]; n]];
+        for level in 1..=depth {
+            let (c, ch): (Vec<Vec<usize>>, Vec<Vec<usize>>) = (0..n)
+                .map(|from| {
+                    (0..n)
+                        .map(|to| {
+                            remote.moves[from][to]
+                                .iter()
+                                .map(|path| sequence_cost(&remote, &costs[level - 1], path))
+                                .enumerate()
+                                .min_by_key(|&(_, cost)| cost)
+                                .map(|(i, cost)| (cost, i))
+                                .unwrap()
+                        })
+                        .unzip()
+                })
+                .unzip();
+            costs.push(c);
+            choice.push(ch);
+        }
+        KeypadChain {
+            door,
+            remote,
+            depth,
+            costs,
+            choice,
+        }
+    }
+
+    fn door_moves(&self, code: &str) -> Vec<&Vec<u8>> {
+        let costs = &self.costs[self.depth];
+        code.bytes()
+            .fold(
+                (Vec::new(), self.door.index(b'A')),
+                |(mut moves, prev), b| {
+                    let next = self.door.index(b);
+                    moves.push(
+                        self.door.moves[prev][next]
+                            .iter()
+                            .min_by_key(|path| sequence_cost(&self.remote, costs, path))
+                            .unwrap(),
+                    );
+                    (moves, next)
+                },
+            )
+            .0
+    }
+
+    pub fn shortest(&self, code: &str) -> usize {
+        let costs = &self.costs[self.depth];
+        self.door_moves(code)
+            .into_iter()
+            .map(|path| sequence_cost(&self.remote, costs, path))
+            .sum()
+    }
+
+    // Top level presses for `code`, the length grows exponentially with depth
+    pub fn sequence(&self, code: &str) -> String {
+        let mut presses = Vec::new();
+        for path in self.door_moves(code) {
+            self.expand(self.depth, path, &mut presses);
+        }
+        String::from_utf8(presses).unwrap()
+    }
+
+    fn expand(&self, level: usize, path: &[u8], presses: &mut Vec<u8>) {
+        if level == 0 {
+            presses.extend_from_slice(path);
+        } else {
+            let mut prev = self.remote.index(b'A');
+            for &b in path {
+                let next = self.remote.index(b);
+                let chosen = &self.remote.moves[prev][next][self.choice[level][prev][next]];
+                self.expand(level - 1, chosen, presses);
+                prev = next;
+            }
+        }
+    }
+
+    pub fn complexity(&self, code: &str) -> usize {
+        let value = code
+            .trim_end_matches(|c: char| !c.is_ascii_digit())
+            .parse::<usize>()
+            .unwrap();
+        value * self.shortest(code)
+    }
+}
+
+fn sequence_cost(remote: &Keypad, costs: &[Vec<usize>], path: &[u8]) -> usize {
+    path.iter()
+        .fold((0, remote.index(b'A')), |(cost, prev), &b| {
+            let next = remote.index(b);
+            (cost + costs[prev][next], next)
+        })
+        .0
+}
+
+#[aoc(day21, part1, CHAIN)]
+pub(crate) fn solver_part1_chain(input: &Input) -> usize {
+    let chain = KeypadChain::new(Keypad::numeric(), Keypad::directional(), 2);
+    input.lines().map(|line| chain.complexity(line)).sum()
+}
+
+#[aoc(day21, part2, CHAIN)]
+pub(crate) fn solver_part2_chain(input: &Input) -> usize {
+    let chain = KeypadChain::new(Keypad::numeric(), Keypad::directional(), 25);
+    input.lines().map(|line| chain.complexity(line)).sum()
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_chain;
     use super::solver_part2;
+    use super::solver_part2_chain;
+    use super::Keypad;
+    use super::KeypadChain;
 
     static INPUT: &str = "029A
 980A
@@ -576,4 +811,35 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 0)
     }
+
+    #[test]
+    fn part1_chain() {
+        assert_eq!(solver_part1_chain(&input_generator(INPUT)), 126384)
+    }
+
+    #[test]
+    fn part2_chain() {
+        assert_eq!(solver_part2_chain(&input_generator(INPUT)), 154115708116294)
+    }
+
+    #[test]
+    fn sequence() {
+        let numeric = Keypad::numeric();
+        let directional = Keypad::directional();
+        let chain = KeypadChain::new(numeric.clone(), directional.clone(), 2);
+        let presses = chain.sequence("029A");
+        assert_eq!(presses.len(), 68);
+        assert_eq!(chain.shortest("029A"), 68);
+
+        // Type it back through both robots and the door
+        let robot = directional.decode(presses.as_bytes()).unwrap();
+        let robot = directional.decode(&robot).unwrap();
+        assert_eq!(numeric.decode(&robot).unwrap(), b"029A");
+    }
+
+    #[test]
+    fn no_robots() {
+        let chain = KeypadChain::new(Keypad::numeric(), Keypad::directional(), 0);
+        assert_eq!(chain.sequence("029A"), "<A^A>^^AvvvA");
+    }
 }
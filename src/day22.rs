@@ -6,10 +6,17 @@ use rayon::prelude::*;
 type Input = String;
 
 #[aoc_generator(day22)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
+pub fn parse_secrets(input: &str) -> Vec<u32> {
+    input
+        .lines()
+        .map(|line| line.parse::<u32>().unwrap())
+        .collect()
+}
+
 fn round(mut secret: i64) -> i64 {
     secret ^= secret << 6;
     secret &= 0xFFFFFF;
@@ -20,15 +27,14 @@ fn round(mut secret: i64) -> i64 {
 }
 
 pub fn chunk_round<const C: usize>(secret: &[i32; C]) -> [i32; C] {
-    // secret.map(|mut secret| {
-    //     secret ^= secret << 6;
-    //     secret &= 0xFFFFFF;
-    //     secret ^= secret >> 5;
-    //     secret &= 0xFFFFFF;
-    //     secret ^= secret << 11;
-    //     secret & 0xFFFFFF
-    // })
-    secret.map(|s| s + 30)
+    secret.map(|mut secret| {
+        secret ^= secret << 6;
+        secret &= 0xFFFFFF;
+        secret ^= secret >> 5;
+        secret &= 0xFFFFFF;
+        secret ^= secret << 11;
+        secret & 0xFFFFFF
+    })
 }
 
 pub fn chunk_round_8(secret: &[i32; 32]) -> [i32; 32] {
@@ -66,7 +72,7 @@ fn changes<const N: usize>(prev: i64, prices: &[i8; N]) -> [i8; N] {
 }
 
 #[aoc(day22, part1)]
-fn solver_part1(input: &Input) -> i64 {
+pub(crate) fn solver_part1(input: &Input) -> i64 {
     input
         .lines()
         .map(|line| {
@@ -77,7 +83,7 @@ fn solver_part1(input: &Input) -> i64 {
 }
 
 #[aoc(day22, part1, BULK)]
-fn solver_part1_bulk(input: &Input) -> i64 {
+pub(crate) fn solver_part1_bulk(input: &Input) -> i64 {
     let secrets = input
         .lines()
         .map(|line| line.parse::<u32>().unwrap() as i64)
@@ -86,7 +92,7 @@ fn solver_part1_bulk(input: &Input) -> i64 {
 }
 
 #[aoc(day22, part1, CHUNK)]
-fn solver_part1_chunk(input: &Input) -> i64 {
+pub(crate) fn solver_part1_chunk(input: &Input) -> i64 {
     let secrets = input
         .lines()
         .map(|line| line.parse::<u32>().unwrap() as i32)
@@ -111,7 +117,7 @@ fn solver_part1_chunk(input: &Input) -> i64 {
 }
 
 #[aoc(day22, part1, CHUNK_PARALLEL)]
-fn solver_part1_chunk_parallel(input: &Input) -> i64 {
+pub(crate) fn solver_part1_chunk_parallel(input: &Input) -> i64 {
     let secrets = input
         .lines()
         .map(|line| line.parse::<u32>().unwrap() as i32)
@@ -136,6 +142,129 @@ fn solver_part1_chunk_parallel(input: &Input) -> i64 {
         + sum
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lanes {
+    Scalar,
+    Sse2,
+    Avx2,
+    Avx512,
+}
+
+impl Lanes {
+    // Every implementation this cpu can run, widest last
+    pub fn available() -> Vec<Lanes> {
+        #[allow(unused_mut)]
+        let mut lanes = vec![Lanes::Scalar];
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("sse2") {
+                lanes.push(Lanes::Sse2);
+            }
+            if is_x86_feature_detected!("avx2") {
+                lanes.push(Lanes::Avx2);
+            }
+            if is_x86_feature_detected!("avx512f") {
+                lanes.push(Lanes::Avx512);
+            }
+        }
+        lanes
+    }
+
+    pub fn detect() -> Lanes {
+        *Lanes::available().last().unwrap()
+    }
+}
+
+// Runs `rounds` rounds on every secret in place, falling back to scalar for lanes this cpu lacks
+pub fn simd_rounds(secrets: &mut [i32], rounds: usize, lanes: Lanes) {
+    let done = if Lanes::available().contains(&lanes) {
+        match lanes {
+            Lanes::Scalar => 0,
+            #[cfg(target_arch = "x86_64")]
+            Lanes::Sse2 => unsafe { x86::rounds_sse2(secrets, rounds) },
+            #[cfg(target_arch = "x86_64")]
+            Lanes::Avx2 => unsafe { x86::rounds_avx2(secrets, rounds) },
+            #[cfg(target_arch = "x86_64")]
+            Lanes::Avx512 => unsafe { x86::rounds_avx512(secrets, rounds) },
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => 0,
+        }
+    } else {
+        0
+    };
+    for secret in &mut secrets[done..] {
+        for _ in 0..rounds {
+            *secret = round(*secret as i64) as i32;
+        }
+    }
+}
+
+// Each returns how many secrets it handled, the remainder that doesn't fill a register is left alone.
+// Safety: the caller must check the cpu supports the target feature
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn rounds_sse2(secrets: &mut [i32], rounds: usize) -> usize {
+        let mask = _mm_set1_epi32(0xFFFFFF);
+        let done = secrets.len() / 4 * 4;
+        for chunk in secrets.chunks_exact_mut(4) {
+            let mut v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+            for _ in 0..rounds {
+                v = _mm_and_si128(_mm_xor_si128(v, _mm_slli_epi32(v, 6)), mask);
+                v = _mm_xor_si128(v, _mm_srli_epi32(v, 5));
+                v = _mm_and_si128(_mm_xor_si128(v, _mm_slli_epi32(v, 11)), mask);
+            }
+            _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, v);
+        }
+        done
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rounds_avx2(secrets: &mut [i32], rounds: usize) -> usize {
+        let mask = _mm256_set1_epi32(0xFFFFFF);
+        let done = secrets.len() / 8 * 8;
+        for chunk in secrets.chunks_exact_mut(8) {
+            let mut v = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+            for _ in 0..rounds {
+                v = _mm256_and_si256(_mm256_xor_si256(v, _mm256_slli_epi32(v, 6)), mask);
+                v = _mm256_xor_si256(v, _mm256_srli_epi32(v, 5));
+                v = _mm256_and_si256(_mm256_xor_si256(v, _mm256_slli_epi32(v, 11)), mask);
+            }
+            _mm256_storeu_si256(chunk.as_mut_ptr() as *mut __m256i, v);
+        }
+        done
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub unsafe fn rounds_avx512(secrets: &mut [i32], rounds: usize) -> usize {
+        let mask = _mm512_set1_epi32(0xFFFFFF);
+        let done = secrets.len() / 16 * 16;
+        for chunk in secrets.chunks_exact_mut(16) {
+            let mut v = _mm512_loadu_si512(chunk.as_ptr() as *const __m512i);
+            for _ in 0..rounds {
+                v = _mm512_and_si512(_mm512_xor_si512(v, _mm512_slli_epi32(v, 6)), mask);
+                v = _mm512_xor_si512(v, _mm512_srli_epi32(v, 5));
+                v = _mm512_and_si512(_mm512_xor_si512(v, _mm512_slli_epi32(v, 11)), mask);
+            }
+            _mm512_storeu_si512(chunk.as_mut_ptr() as *mut __m512i, v);
+        }
+        done
+    }
+}
+
+// "cargo aoc bench -d 22 -p 1" compares this against BULK
+#[aoc(day22, part1, SIMD)]
+pub(crate) fn solver_part1_simd(input: &Input) -> i64 {
+    let mut secrets = parse_secrets(input)
+        .into_iter()
+        .map(|v| v as i32)
+        .collect_vec();
+    simd_rounds(&mut secrets, 2000, Lanes::detect());
+    secrets.into_iter().map(|v| v as i64).sum()
+}
+
 // #[aoc(day22, part1, TEST)]
 // #[inline(never)]
 // fn solver_part1_test(input: &Input) -> u32 {
@@ -195,7 +324,7 @@ fn get_bananas<const N: usize>(seq: &[i8; 4], change: &[i8; N], prices: &[i8; N]
 }
 
 #[aoc(day22, part2)]
-fn solver_part2(input: &Input) -> i32 {
+pub(crate) fn solver_part2(input: &Input) -> i32 {
     let secrets = input
         .lines()
         .map(|line| line.parse::<u32>().unwrap() as i64)
@@ -223,11 +352,100 @@ fn solver_part2(input: &Input) -> i32 {
         .0
 }
 
+// Every run of four price changes, each change in -9..=9 as a base 19 digit
+pub const SEQUENCES: usize = 19 * 19 * 19 * 19;
+
+pub fn encode(changes: [i8; 4]) -> usize {
+    changes
+        .iter()
+        .fold(0, |index, &change| index * 19 + (change + 9) as usize)
+}
+
+pub fn decode(mut index: usize) -> [i8; 4] {
+    let mut changes = [0; 4];
+    for change in changes.iter_mut().rev() {
+        *change = (index % 19) as i8 - 9;
+        index /= 19;
+    }
+    changes
+}
+
+// Bananas every change sequence would earn across all buyers, filled in one pass per buyer
+pub struct Market {
+    totals: Vec<u32>,
+}
+
+impl Market {
+    pub fn new(secrets: &[i64], rounds: usize) -> Self {
+        let mut totals = vec![0; SEQUENCES];
+        let mut seen = vec![0u64; SEQUENCES.div_ceil(64)];
+        for &secret in secrets {
+            seen.fill(0);
+            let mut secret = secret;
+            let mut price = secret % 10;
+            let mut index = 0;
+            for i in 0..rounds {
+                secret = round(secret);
+                let next = secret % 10;
+                index = (index * 19 + (next - price + 9) as usize) % SEQUENCES;
+                price = next;
+                // Only the first time a buyer sees a sequence counts
+                if i >= 3 && seen[index / 64] & (1 << (index % 64)) == 0 {
+                    seen[index / 64] |= 1 << (index % 64);
+                    totals[index] += price as u32;
+                }
+            }
+        }
+        Market { totals }
+    }
+
+    pub fn total(&self, changes: [i8; 4]) -> u32 {
+        self.totals[encode(changes)]
+    }
+
+    // The `n` best sequences, most bananas first and ties in sequence order
+    pub fn top(&self, n: usize) -> Vec<([i8; 4], u32)> {
+        self.totals
+            .iter()
+            .enumerate()
+            .filter(|(_, &total)| total > 0)
+            .sorted_by_key(|&(index, &total)| (std::cmp::Reverse(total), index))
+            .take(n)
+            .map(|(index, &total)| (decode(index), total))
+            .collect()
+    }
+
+    pub fn best(&self) -> Option<([i8; 4], u32)> {
+        self.top(1).pop()
+    }
+}
+
+#[aoc(day22, part2, DENSE)]
+pub(crate) fn solver_part2_dense(input: &Input) -> u32 {
+    let secrets = parse_secrets(input)
+        .into_iter()
+        .map(|v| v as i64)
+        .collect_vec();
+    Market::new(&secrets, 2000).best().unwrap().1
+}
+
 #[cfg(test)]
 mod tests {
+    use super::chunk_round;
+    use super::decode;
+    use super::encode;
     use super::input_generator;
+    use super::round;
+    use super::simd_rounds;
     use super::solver_part1;
+    use super::solver_part1_chunk;
+    use super::solver_part1_chunk_parallel;
+    use super::solver_part1_simd;
     use super::solver_part2;
+    use super::solver_part2_dense;
+    use super::Lanes;
+    use super::Market;
+    use super::SEQUENCES;
 
     static INPUT: &str = "1
 10
@@ -248,4 +466,66 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT2)), 23)
     }
+
+    #[test]
+    fn market() {
+        assert_eq!(solver_part2_dense(&input_generator(INPUT2)), 23);
+
+        let market = Market::new(&[1, 2, 3, 2024], 2000);
+        assert_eq!(market.best(), Some(([-2, 1, -1, 3], 23)));
+        let top = market.top(5);
+        assert_eq!(top.len(), 5);
+        assert!(top.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        // The ten prices of buyer 123 from the puzzle text
+        let market = Market::new(&[123], 9);
+        assert_eq!(market.total([-1, -1, 0, 2]), 6);
+        assert_eq!(market.total([-3, 6, -1, -1]), 4);
+        assert_eq!(market.top(10).len(), 6);
+    }
+
+    #[test]
+    fn encoding() {
+        for changes in [
+            [-9, -9, -9, -9],
+            [9, 9, 9, 9],
+            [-2, 1, -1, 3],
+            [0, 5, -7, 1],
+        ] {
+            assert_eq!(decode(encode(changes)), changes);
+        }
+        assert_eq!(encode([9, 9, 9, 9]), SEQUENCES - 1);
+    }
+
+    #[test]
+    fn lanes() {
+        for solver in [
+            solver_part1_chunk,
+            solver_part1_chunk_parallel,
+            solver_part1_simd,
+        ] {
+            assert_eq!(solver(&input_generator(INPUT)), 37327623);
+        }
+
+        // 37 secrets leave a remainder after every register width
+        let secrets = vec![
+            1, 10, 100, 2024, 123, 15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544,
+            12249484, 7753432, 5908254, 0, 16777215, 8388608, 42, 999999, 314159, 271828, 1618033,
+            1414213, 1732050, 2236067, 2645751, 3162277, 4096, 65535, 65536, 1048575, 7, 77, 777,
+            7777, 77777,
+        ];
+        let expected = secrets
+            .iter()
+            .map(|&s| (0..100).fold(s as i64, |s, _| round(s)) as i32)
+            .collect::<Vec<_>>();
+        for lanes in Lanes::available() {
+            let mut result = secrets.clone();
+            simd_rounds(&mut result, 100, lanes);
+            assert_eq!(result, expected, "{lanes:?}");
+        }
+
+        let chunk = <[i32; 32]>::try_from(&secrets[..32]).unwrap();
+        let stepped = chunk.map(|s| round(s as i64) as i32);
+        assert_eq!(chunk_round(&chunk), stepped);
+    }
 }
@@ -4,7 +4,7 @@ use aoc_runner_derive::aoc_generator;
 type Input = String;
 
 #[aoc_generator(day23)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -31,13 +31,24 @@ fn hash(s: &[u8; 2]) -> (usize, bool) {
     (ind + (s[1] - 97) as usize, s[0] == b't')
 }
 
+// A connection is two lowercase names, "ab-cd"
+pub(crate) fn parse_link(line: &str) -> ((usize, bool), (usize, bool)) {
+    let bytes = line.as_bytes();
+    let name = |i: usize| bytes[i].is_ascii_lowercase() && bytes[i + 1].is_ascii_lowercase();
+    if bytes.len() != 5 || bytes[2] != b'-' || !name(0) || !name(3) {
+        panic!("Connection {line:?} isn't two lowercase names");
+    }
+    (
+        hash(&bytes[0..2].try_into().unwrap()),
+        hash(&bytes[3..5].try_into().unwrap()),
+    )
+}
+
 #[aoc(day23, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut nodes: [Option<Node>; 768] = [const { None }; 768];
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let (ind1, t1) = hash(&bytes[0..2].try_into().unwrap());
-        let (ind2, t2) = hash(&bytes[3..5].try_into().unwrap());
+        let ((ind1, t1), (ind2, t2)) = parse_link(line);
         if let Some(node1) = &mut nodes[ind1] {
             node1.add_connection(ind2);
         } else {
@@ -77,9 +88,8 @@ fn solver_part1(input: &Input) -> usize {
         / 6
 }
 
-fn ordered_hashes(bytes: &[u8]) -> ((usize, bool), (usize, bool)) {
-    let a = hash(&bytes[0..2].try_into().unwrap());
-    let b = hash(&bytes[3..5].try_into().unwrap());
+fn ordered_hashes(line: &str) -> ((usize, bool), (usize, bool)) {
+    let (a, b) = parse_link(line);
     if a.0 > b.0 {
         (b, a)
     } else {
@@ -88,11 +98,10 @@ fn ordered_hashes(bytes: &[u8]) -> ((usize, bool), (usize, bool)) {
 }
 
 #[aoc(day23, part1, FASTER)]
-fn solver_part1_faster(input: &Input) -> usize {
+pub(crate) fn solver_part1_faster(input: &Input) -> usize {
     let mut nodes: [Option<Node>; 768] = [const { None }; 768];
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let ((ind1, t1), (ind2, t2)) = ordered_hashes(bytes);
+        let ((ind1, t1), (ind2, t2)) = ordered_hashes(line);
 
         if let Some(node1) = &mut nodes[ind1] {
             node1.add_connection(ind2);
@@ -130,11 +139,10 @@ fn solver_part1_faster(input: &Input) -> usize {
 }
 
 #[aoc(day23, part1, SORTED)]
-fn solver_part1_sorted(input: &Input) -> usize {
+pub(crate) fn solver_part1_sorted(input: &Input) -> usize {
     let mut nodes: [Option<Node>; 768] = [const { None }; 768];
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let ((ind1, t1), (ind2, t2)) = ordered_hashes(bytes);
+        let ((ind1, t1), (ind2, t2)) = ordered_hashes(line);
 
         if let Some(node1) = &mut nodes[ind1] {
             node1.add_connection(ind2);
@@ -185,11 +193,10 @@ fn hash2id(ind: usize) -> String {
 }
 
 #[aoc(day23, part2)]
-fn solver_part2(input: &Input) -> String {
+pub(crate) fn solver_part2(input: &Input) -> String {
     let mut nodes: [Option<Node>; 768] = [const { None }; 768];
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let ((ind1, t1), (ind2, t2)) = ordered_hashes(bytes);
+        let ((ind1, t1), (ind2, t2)) = ordered_hashes(line);
 
         if let Some(node1) = &mut nodes[ind1] {
             node1.add_connection(ind2);
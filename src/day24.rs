@@ -7,7 +7,7 @@ use itertools::Itertools;
 type Input = String;
 
 #[derive(Debug, Clone, Copy)]
-struct Gate<'a> {
+pub(crate) struct Gate<'a> {
     inputs: (GateInput<'a>, GateInput<'a>),
     op: GateOP,
     value: Option<bool>,
@@ -47,7 +47,7 @@ enum GateOP {
     Or,
 }
 
-fn parse(input: &str) -> (FxHashMap<&str, bool>, FxHashMap<&str, Gate>, u32) {
+pub(crate) fn parse(input: &str) -> (FxHashMap<&str, bool>, FxHashMap<&str, Gate<'_>>, u32) {
     let mut lines = input.lines();
     let signals: FxHashMap<_, _> = lines
         .take_while_ref(|line| !line.is_empty())
@@ -126,7 +126,7 @@ fn parse2(input: &str) -> (FxHashMap<&str, Gate2>, u32) {
 }
 
 #[aoc_generator(day24)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -160,7 +160,7 @@ fn get_gate_value(key: &str, gates: &mut FxHashMap<&str, Gate>) -> bool {
 }
 
 #[aoc(day24, part1)]
-fn solver_part1(input: &Input) -> u64 {
+pub(crate) fn solver_part1(input: &Input) -> u64 {
     let (_, mut gates, max) = parse(input);
     let mut out = 0u64;
     for z in 0..=max {
@@ -173,7 +173,7 @@ fn solver_part1(input: &Input) -> u64 {
 }
 
 #[aoc(day24, part2)]
-fn solver_part2(input: &Input) -> String {
+pub(crate) fn solver_part2(input: &Input) -> String {
     let (gates, _) = parse2(input);
 
     let zgates = gates
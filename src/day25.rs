@@ -17,12 +17,12 @@ impl Lock {
     }
 }
 #[aoc_generator(day25)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[aoc(day25, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let (locks, keys): (Vec<_>, Vec<_>) =
         input
             .lines()
@@ -52,16 +52,154 @@ fn solver_part1(input: &Input) -> usize {
         .count()
 }
 
-#[aoc(day25, part2)]
-fn solver_part2(_input: &Input) -> u32 {
-    0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchematicError {
+    Empty,
+    // Blocks are numbered from 0 in input order
+    WrongSize(usize),
+    InvalidCharacter(usize),
+    NeitherLockNorKey(usize),
+    BrokenColumn(usize),
+    // A lock pin reaching the bottom row or a key pin reaching the top row
+    PinTooLong(usize),
+    // Pins are stored as u8, so at most 257 rows
+    TooTall,
+}
+
+// Locks and keys as pin heights, not counting the solid row at the top of a lock or bottom of a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematics {
+    width: usize,
+    height: usize,
+    locks: Vec<Vec<u8>>,
+    keys: Vec<Vec<u8>>,
+}
+
+impl Schematics {
+    // All blocks must match the size of the first
+    pub fn parse(input: &str) -> Result<Schematics, SchematicError> {
+        let blocks = input
+            .lines()
+            .chunk_by(|l| l.trim().is_empty())
+            .into_iter()
+            .filter(|(blank, _)| !blank)
+            .map(|(_, lines)| lines.map(|l| l.trim().as_bytes()).collect_vec())
+            .collect_vec();
+        let first = blocks.first().ok_or(SchematicError::Empty)?;
+        let (width, height) = (first[0].len(), first.len());
+        let mut schematics = Schematics {
+            width,
+            height,
+            locks: Vec::new(),
+            keys: Vec::new(),
+        };
+        if height > u8::MAX as usize + 2 {
+            return Err(SchematicError::TooTall);
+        }
+        for (i, block) in blocks.iter().enumerate() {
+            if height < 2
+                || width == 0
+                || block.len() != height
+                || block.iter().any(|l| l.len() != width)
+            {
+                return Err(SchematicError::WrongSize(i));
+            }
+            if block
+                .iter()
+                .flat_map(|l| l.iter())
+                .any(|&b| b != b'#' && b != b'.')
+            {
+                return Err(SchematicError::InvalidCharacter(i));
+            }
+            let solid = |row: &[u8]| row.iter().all(|&b| b == b'#');
+            let (lock, rows) = match (solid(block[0]), solid(block[height - 1])) {
+                (true, false) => (true, block.iter().collect_vec()),
+                (false, true) => (false, block.iter().rev().collect_vec()),
+                _ => return Err(SchematicError::NeitherLockNorKey(i)),
+            };
+            // Reading from the solid edge, each column must be a run of '#' then only '.'
+            let mut pins = Vec::with_capacity(width);
+            for x in 0..width {
+                let run = rows.iter().take_while(|row| row[x] == b'#').count();
+                if rows[run..].iter().any(|row| row[x] == b'#') {
+                    return Err(SchematicError::BrokenColumn(i));
+                }
+                if run == height {
+                    return Err(SchematicError::PinTooLong(i));
+                }
+                pins.push((run - 1) as u8);
+            }
+            if lock {
+                schematics.locks.push(pins);
+            } else {
+                schematics.keys.push(pins);
+            }
+        }
+        Ok(schematics)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn locks(&self) -> &[Vec<u8>] {
+        &self.locks
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    // Every (lock, key) pair with no column overlapping, in lock then key order.
+    // For each column and height there's a bitset of the keys no taller than it, so a lock's
+    // keys are the AND of one bitset per column
+    pub fn fits(&self) -> Vec<(usize, usize)> {
+        let space = self.height - 2;
+        let words = self.keys.len().div_ceil(64);
+        let mut at_most = vec![vec![vec![0u64; words]; space + 1]; self.width];
+        for (k, key) in self.keys.iter().enumerate() {
+            for (column, &pin) in at_most.iter_mut().zip(key) {
+                for bits in &mut column[pin as usize..] {
+                    bits[k / 64] |= 1 << (k % 64);
+                }
+            }
+        }
+
+        let mut fits = Vec::new();
+        for (l, lock) in self.locks.iter().enumerate() {
+            let mut bits = vec![u64::MAX; words];
+            for (column, &pin) in at_most.iter().zip(lock) {
+                let allowed = &column[space - pin as usize];
+                bits.iter_mut().zip(allowed).for_each(|(b, a)| *b &= a);
+            }
+            for (w, &word) in bits.iter().enumerate() {
+                let mut word = word;
+                while word != 0 {
+                    fits.push((l, w * 64 + word.trailing_zeros() as usize));
+                    word &= word - 1;
+                }
+            }
+        }
+        fits
+    }
+}
+
+#[aoc(day25, part1, FITTER)]
+pub(crate) fn solver_part1_fitter(input: &Input) -> usize {
+    Schematics::parse(input).unwrap().fits().len()
 }
 
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
-    use super::solver_part2;
+    use super::solver_part1_fitter;
+    use super::SchematicError;
+    use super::Schematics;
 
     static INPUT: &str = "#####
 .####
@@ -109,7 +247,81 @@ mod tests {
     }
 
     #[test]
-    fn part2() {
-        assert_eq!(solver_part2(&input_generator(INPUT)), 0)
+    fn fitter() {
+        assert_eq!(solver_part1_fitter(&input_generator(INPUT)), 3);
+        let schematics = Schematics::parse(INPUT).unwrap();
+        assert_eq!((schematics.width(), schematics.height()), (5, 7));
+        assert_eq!(schematics.locks()[1], [1, 2, 0, 5, 3]);
+        assert_eq!(schematics.keys()[0], [5, 0, 2, 1, 3]);
+        assert_eq!(schematics.fits(), [(0, 2), (1, 1), (1, 2)]);
+
+        // Three columns with two rows of space
+        let small =
+            Schematics::parse("###\n.#.\n...\n...\n\n...\n...\n#..\n###\n\n...\n.#.\n.##\n###")
+                .unwrap();
+        assert_eq!((small.width(), small.height()), (3, 4));
+        assert_eq!(small.fits(), [(0, 0)]);
+    }
+
+    #[test]
+    fn many() {
+        // A lock with pins of 1 but a 0 in the last column, against keys with a single pin
+        // of every height 0 to 3 in every column
+        let mut input = String::from("######\n#####.\n......\n......\n......\n");
+        for i in 0..600 {
+            let (x, h) = (i % 6, i / 6 % 4);
+            input.push('\n');
+            for y in 0..4 {
+                let row = (0..6).map(|c| if c == x && y >= 4 - h { '#' } else { '.' });
+                input.extend(row.chain(['\n']));
+            }
+            input.push_str("######\n");
+        }
+        let schematics = Schematics::parse(&input).unwrap();
+        assert_eq!(schematics.keys().len(), 600);
+        // Pins up to 2 fit the first five columns, any pin fits the last
+        assert_eq!(schematics.fits().len(), 25 * (5 * 3 + 4));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Schematics::parse(""), Err(SchematicError::Empty));
+        assert_eq!(
+            Schematics::parse("##\n..\n\n###\n...\n"),
+            Err(SchematicError::WrongSize(1))
+        );
+        assert_eq!(
+            Schematics::parse("##\n.x"),
+            Err(SchematicError::InvalidCharacter(0))
+        );
+        assert_eq!(
+            Schematics::parse("#.\n.#"),
+            Err(SchematicError::NeitherLockNorKey(0))
+        );
+        assert_eq!(
+            Schematics::parse("##\n..\n#.\n.."),
+            Err(SchematicError::BrokenColumn(0))
+        );
+        assert_eq!(
+            Schematics::parse("###\n#..\n#..\n#.."),
+            Err(SchematicError::PinTooLong(0))
+        );
+        assert_eq!(
+            Schematics::parse("###\n...\n...\n\n.#.\n.#.\n###"),
+            Err(SchematicError::PinTooLong(1))
+        );
+    }
+
+    #[test]
+    fn tall() {
+        // 257 rows leave room for a pin of 255, one more row doesn't fit a u8
+        let lock = |height: usize| {
+            let mut rows = vec!["#"; height - 1];
+            rows.push(".");
+            rows.join("\n")
+        };
+        let schematics = Schematics::parse(&lock(257)).unwrap();
+        assert_eq!(schematics.locks(), [vec![255]]);
+        assert_eq!(Schematics::parse(&lock(258)), Err(SchematicError::TooTall));
     }
 }
@@ -1,3 +1,6 @@
+use std::io;
+use std::io::Read;
+use std::ops::Range;
 use std::str::Bytes;
 
 use aoc_runner_derive::aoc;
@@ -6,12 +9,12 @@ use aoc_runner_derive::aoc_generator;
 type Input = String;
 
 #[aoc_generator(day3)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[aoc(day3, part1)]
-fn solver_part1(input: &Input) -> u32 {
+pub(crate) fn solver_part1(input: &Input) -> u32 {
     let re = regex::Regex::new(r"mul\((\d\d?\d?),(\d\d?\d?)\)").unwrap();
     re.captures_iter(input)
         .map(|c| c[1].parse::<u32>().unwrap() * c[2].parse::<u32>().unwrap())
@@ -29,7 +32,7 @@ enum State {
 }
 
 #[aoc(day3, part1, DFA)]
-fn solver_part1_dfa(input: &Input) -> u32 {
+pub(crate) fn solver_part1_dfa(input: &Input) -> u32 {
     // let re = regex::Regex::new(r"mul\((\d\d?\d?),(\d\d?\d?)\)").unwrap();
     let mut v1 = 0;
     let mut v2 = 0;
@@ -156,7 +159,7 @@ fn find_mul(bytes: &mut Bytes<'_>) -> Option<u32> {
 }
 
 #[aoc(day3, part1, DFA2)]
-fn solver_part1_dfa2(input: &Input) -> u32 {
+pub(crate) fn solver_part1_dfa2(input: &Input) -> u32 {
     let mut sum = 0;
     let mut bytes = input.bytes();
     while let Some(v) = find_mul(&mut bytes) {
@@ -166,7 +169,7 @@ fn solver_part1_dfa2(input: &Input) -> u32 {
 }
 
 #[aoc(day3, part2)]
-fn solver_part2(input: &Input) -> u32 {
+pub(crate) fn solver_part2(input: &Input) -> u32 {
     let re = regex::Regex::new(r"mul\(\d\d?\d?,\d\d?\d?\)|do\(\)|don't\(\)").unwrap();
     let re_mul = regex::Regex::new(r"mul\((\d\d?\d?),(\d\d?\d?)\)").unwrap();
     let mut enable = true;
@@ -185,7 +188,7 @@ fn solver_part2(input: &Input) -> u32 {
 }
 
 #[aoc(day3, part2, SINGLE)]
-fn solver_part2_single(input: &Input) -> u32 {
+pub(crate) fn solver_part2_single(input: &Input) -> u32 {
     let re = regex::Regex::new(r"mul\((\d\d?\d?),(\d\d?\d?)\)|do\(\)|don't\(\)").unwrap();
     let mut enable = true;
     let mut sum = 0;
@@ -273,7 +276,7 @@ fn find_mul_part2(bytes: &mut Bytes<'_>, active: &mut bool) -> Option<u32> {
 }
 
 #[aoc(day3, part2, DFA)]
-fn solver_part2_dfa(input: &Input) -> u32 {
+pub(crate) fn solver_part2_dfa(input: &Input) -> u32 {
     let mut sum = 0;
     let mut bytes = input.bytes();
     let mut active = true;
@@ -283,6 +286,217 @@ fn solver_part2_dfa(input: &Input) -> u32 {
     sum
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Mul(u32, u32),
+    Do,
+    Dont,
+    Custom(String, Vec<u32>),
+}
+
+struct Instruction {
+    name: Vec<u8>,
+    arity: usize,
+}
+
+// A match still in progress. `matched` counts name bytes, then one more once '(' is seen
+struct Partial {
+    instruction: usize,
+    start: u64,
+    matched: usize,
+    args: Vec<u32>,
+    digits: u8,
+}
+
+enum Advance {
+    Continue,
+    Fail,
+    Complete,
+}
+
+// Tokenizes corrupted memory from any reader a chunk at a time. Operands are 1 to 3 digits and
+// every token comes with the byte range it covers
+pub struct Scanner<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    len: usize,
+    cursor: usize,
+    offset: u64,
+    instructions: Vec<Instruction>,
+    partials: Vec<Partial>,
+    // Survivors are moved between the two buffers so neither reallocates
+    spare: Vec<Partial>,
+}
+
+impl<R: Read> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner {
+            reader,
+            buffer: vec![0; 1 << 16].into_boxed_slice(),
+            len: 0,
+            cursor: 0,
+            offset: 0,
+            instructions: Vec::new(),
+            partials: Vec::new(),
+            spare: Vec::new(),
+        }
+        .with_instruction("mul", 2)
+        .with_instruction("do", 0)
+        .with_instruction("don't", 0)
+    }
+
+    // Adds `name(a,b,..)` taking `arity` operands, emitted as `Token::Custom`
+    pub fn with_instruction(mut self, name: &str, arity: usize) -> Self {
+        assert!(
+            !name.is_empty() && !name.contains(['(', ')']),
+            "Invalid instruction name {name:?}"
+        );
+        self.instructions.push(Instruction {
+            name: name.as_bytes().to_vec(),
+            arity,
+        });
+        self
+    }
+
+    fn advance(&self, partial: &mut Partial, b: u8) -> Advance {
+        let instruction = &self.instructions[partial.instruction];
+        let name = instruction.name.len();
+        if partial.matched < name {
+            if instruction.name[partial.matched] != b {
+                return Advance::Fail;
+            }
+            partial.matched += 1;
+            return Advance::Continue;
+        }
+        if partial.matched == name {
+            if b != b'(' {
+                return Advance::Fail;
+            }
+            partial.matched += 1;
+            if instruction.arity > 0 {
+                partial.args.push(0);
+            }
+            return Advance::Continue;
+        }
+        match b {
+            b'0'..=b'9' if partial.digits < 3 && !partial.args.is_empty() => {
+                let arg = partial.args.last_mut().unwrap();
+                *arg = *arg * 10 + (b - b'0') as u32;
+                partial.digits += 1;
+                Advance::Continue
+            }
+            b',' if partial.digits > 0 && partial.args.len() < instruction.arity => {
+                partial.args.push(0);
+                partial.digits = 0;
+                Advance::Continue
+            }
+            b')' if partial.args.len() == instruction.arity
+                && (instruction.arity == 0 || partial.digits > 0) =>
+            {
+                Advance::Complete
+            }
+            _ => Advance::Fail,
+        }
+    }
+
+    fn token(&self, partial: Partial) -> Token {
+        match (partial.instruction, partial.args.as_slice()) {
+            (0, &[a, b]) => Token::Mul(a, b),
+            (1, _) => Token::Do,
+            (2, _) => Token::Dont,
+            (i, _) => Token::Custom(
+                String::from_utf8_lossy(&self.instructions[i].name).into_owned(),
+                partial.args,
+            ),
+        }
+    }
+
+    // Feeds one byte, returning the earliest starting instruction it completes
+    fn feed(&mut self, b: u8) -> Option<(Token, Range<u64>)> {
+        let offset = self.offset;
+        self.offset += 1;
+        for instruction in 0..self.instructions.len() {
+            self.partials.push(Partial {
+                instruction,
+                start: offset,
+                matched: 0,
+                args: Vec::new(),
+                digits: 0,
+            });
+        }
+        let mut complete: Option<Partial> = None;
+        let mut partials = std::mem::take(&mut self.spare);
+        std::mem::swap(&mut partials, &mut self.partials);
+        for mut partial in partials.drain(..) {
+            match self.advance(&mut partial, b) {
+                Advance::Continue => self.partials.push(partial),
+                Advance::Fail => (),
+                Advance::Complete => {
+                    if complete.as_ref().is_none_or(|c| partial.start < c.start) {
+                        complete = Some(partial);
+                    }
+                }
+            }
+        }
+        self.spare = partials;
+        // ')' can't appear in a name, so nothing else survives a completed match
+        let partial = complete?;
+        self.partials.clear();
+        let span = partial.start..offset + 1;
+        Some((self.token(partial), span))
+    }
+}
+
+impl<R: Read> Iterator for Scanner<R> {
+    type Item = io::Result<(Token, Range<u64>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.cursor < self.len {
+                let b = self.buffer[self.cursor];
+                self.cursor += 1;
+                if let Some(token) = self.feed(b) {
+                    return Some(Ok(token));
+                }
+            }
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(len) => {
+                    self.len = len;
+                    self.cursor = 0;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[aoc(day3, part1, STREAM)]
+pub(crate) fn solver_part1_stream(input: &Input) -> u32 {
+    Scanner::new(input.as_bytes())
+        .map(|token| match token.unwrap().0 {
+            Token::Mul(a, b) => a * b,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[aoc(day3, part2, STREAM)]
+pub(crate) fn solver_part2_stream(input: &Input) -> u32 {
+    let mut enable = true;
+    let mut sum = 0;
+    for token in Scanner::new(input.as_bytes()) {
+        match token.unwrap().0 {
+            Token::Mul(a, b) if enable => sum += a * b,
+            Token::Do => enable = true,
+            Token::Dont => enable = false,
+            _ => (),
+        }
+    }
+    sum
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day3::solver_part1_dfa;
@@ -292,6 +506,11 @@ mod tests {
 
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_stream;
+    use super::solver_part2_stream;
+    use super::Scanner;
+    use super::Token;
+    use std::io::Read;
 
     static INPUT: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
     static INPUT2: &str =
@@ -321,4 +540,69 @@ mod tests {
     fn part2_dfa() {
         assert_eq!(solver_part2_dfa(&input_generator(INPUT2)), 48)
     }
+
+    // Hands out a single byte per read so every token straddles a chunk boundary
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Some((&b, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = b;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn stream() {
+        assert_eq!(solver_part1_stream(&input_generator(INPUT)), 161);
+        assert_eq!(solver_part2_stream(&input_generator(INPUT2)), 48);
+
+        let tokens = Scanner::new(Trickle(INPUT2.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            tokens,
+            [
+                (Token::Mul(2, 4), 1..9),
+                (Token::Dont, 20..27),
+                (Token::Mul(5, 5), 28..36),
+                (Token::Mul(11, 8), 48..57),
+                (Token::Do, 59..63),
+                (Token::Mul(8, 5), 64..72),
+            ]
+        );
+        let spans = tokens
+            .iter()
+            .map(|t| &INPUT2[t.1.start as usize..t.1.end as usize]);
+        assert!(spans.eq([
+            "mul(2,4)",
+            "don't()",
+            "mul(5,5)",
+            "mul(11,8)",
+            "do()",
+            "mul(8,5)"
+        ]));
+    }
+
+    #[test]
+    fn custom_instructions() {
+        let input = "mul(1234,5)add(1,2,3)mmul(2,3)nop()nop(1)add(4,5,6))";
+        let tokens = Scanner::new(input.as_bytes())
+            .with_instruction("add", 3)
+            .with_instruction("nop", 0)
+            .map(|t| t.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                Token::Custom("add".to_string(), vec![1, 2, 3]),
+                Token::Mul(2, 3),
+                Token::Custom("nop".to_string(), vec![]),
+                Token::Custom("add".to_string(), vec![4, 5, 6]),
+            ]
+        );
+    }
 }
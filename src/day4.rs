@@ -1,12 +1,13 @@
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
+use aoc_tools::grid::Coord;
 use aoc_tools::grid::Grid;
 use itertools::Itertools;
 
 type Input = String;
 
 #[aoc_generator(day4)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -15,13 +16,13 @@ fn parse_char(input: &str) -> Grid<char> {
     Grid::from_iter(&mut input.lines().flat_map(|l| l.chars()), width)
 }
 
-fn parse_byte(input: &str) -> Grid<u8> {
+pub(crate) fn parse_byte(input: &str) -> Grid<u8> {
     let width = input.lines().next().unwrap().len();
     Grid::from_iter(&mut input.lines().flat_map(|l| l.bytes()), width)
 }
 
 #[aoc(day4, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let grid = parse_char(input);
     grid.coord_iter()
         .flat_map(|coord| {
@@ -35,7 +36,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day4, part1, FAST)]
-fn solver_part1_fast(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast(input: &Input) -> usize {
     let grid = parse_char(input);
     grid.coord_iter()
         .flat_map(|coord| {
@@ -54,7 +55,7 @@ fn solver_part1_fast(input: &Input) -> usize {
 }
 
 #[aoc(day4, part1, FAST_FLATTEN)]
-fn solver_part1_fast_flatten(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_flatten(input: &Input) -> usize {
     let grid = parse_char(input);
     grid.coord_iter()
         .flat_map(|coord| {
@@ -76,7 +77,7 @@ fn solver_part1_fast_flatten(input: &Input) -> usize {
 }
 
 #[aoc(day4, part1, FAST_FLATTEN_BYTES)]
-fn solver_part1_fast_flatten_bytes(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_flatten_bytes(input: &Input) -> usize {
     let grid = parse_byte(input);
     grid.coord_iter()
         .flat_map(|coord| {
@@ -98,7 +99,7 @@ fn solver_part1_fast_flatten_bytes(input: &Input) -> usize {
 }
 
 #[aoc(day4, part1, FAST_FLATTEN_BYTES_FILTER)]
-fn solver_part1_fast_flatten_bytes_filter(input: &Input) -> usize {
+pub(crate) fn solver_part1_fast_flatten_bytes_filter(input: &Input) -> usize {
     let grid = parse_byte(input);
     grid.iter()
         .filter(|(_, &b)| b == b'X')
@@ -121,7 +122,7 @@ fn solver_part1_fast_flatten_bytes_filter(input: &Input) -> usize {
 }
 
 #[aoc(day4, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let grid = parse_char(input);
     grid.coord_iter()
         .filter(|coord| {
@@ -148,7 +149,7 @@ fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day4, part2, ITER)]
-fn solver_part2_iter(input: &Input) -> usize {
+pub(crate) fn solver_part2_iter(input: &Input) -> usize {
     let target = vec![b'M', b'M', b'S', b'S'];
     let grid = parse_byte(input);
     grid.iter()
@@ -174,7 +175,7 @@ fn solver_part2_iter(input: &Input) -> usize {
 }
 
 #[aoc(day4, part2, ITER_ARRAY)]
-fn solver_part2_iter_array(input: &Input) -> usize {
+pub(crate) fn solver_part2_iter_array(input: &Input) -> usize {
     let targets = [
         [b'M', b'M', b'S', b'S'],
         [b'M', b'S', b'S', b'M'],
@@ -199,7 +200,7 @@ fn solver_part2_iter_array(input: &Input) -> usize {
 }
 
 #[aoc(day4, part2, ITER_INNER)]
-fn solver_part2_iter_inner(input: &Input) -> usize {
+pub(crate) fn solver_part2_iter_inner(input: &Input) -> usize {
     let grid = parse_byte(input);
     grid.iter()
         .filter(|(coord, b)| {
@@ -228,7 +229,7 @@ fn solver_part2_iter_inner(input: &Input) -> usize {
 }
 
 #[aoc(day4, part2, ITER_INNER_ARRAY)]
-fn solver_part2_iter_inner_array(input: &Input) -> usize {
+pub(crate) fn solver_part2_iter_inner_array(input: &Input) -> usize {
     let targets = [
         [b'M', b'M', b'S', b'S'],
         [b'M', b'S', b'S', b'M'],
@@ -253,11 +254,240 @@ fn solver_part2_iter_inner_array(input: &Input) -> usize {
         })
         .count()
 }
+
+// Relative offsets and the byte each must hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil(Vec<((i32, i32), u8)>);
+
+impl Stencil {
+    pub fn new(cells: Vec<((i32, i32), u8)>) -> Self {
+        Stencil(cells)
+    }
+
+    // Reads left to right from the origin
+    pub fn word(word: &str) -> Self {
+        Stencil(
+            word.bytes()
+                .enumerate()
+                .map(|(i, b)| ((i as i32, 0), b))
+                .collect(),
+        )
+    }
+
+    // A block of text with '.' for cells that can hold anything, anchored at its top left
+    pub fn parse(pattern: &str) -> Self {
+        Stencil(
+            pattern
+                .lines()
+                .enumerate()
+                .flat_map(|(y, l)| {
+                    l.bytes()
+                        .enumerate()
+                        .filter(|(_, b)| *b != b'.')
+                        .map(move |(x, b)| ((x as i32, y as i32), b))
+                })
+                .collect(),
+        )
+    }
+
+    // Rotates clockwise by an eighth turn, walking each offset round its square ring. A quarter
+    // turn is an exact rotation, an eighth turn takes a straight line onto the diagonal
+    fn eighth_turn((mut x, mut y): (i32, i32)) -> (i32, i32) {
+        let r = x.abs().max(y.abs());
+        for _ in 0..r {
+            if x == r && y < r {
+                y += 1;
+            } else if y == r && x > -r {
+                x -= 1;
+            } else if x == -r && y > -r {
+                y -= 1;
+            } else {
+                x += 1;
+            }
+        }
+        (x, y)
+    }
+
+    fn oriented(&self, orientation: Orientation) -> Stencil {
+        Stencil(
+            self.0
+                .iter()
+                .map(|&((x, y), b)| {
+                    let mut offset = if orientation.reflected {
+                        (-x, y)
+                    } else {
+                        (x, y)
+                    };
+                    for _ in 0..orientation.eighth_turns {
+                        offset = Stencil::eighth_turn(offset);
+                    }
+                    (offset, b)
+                })
+                .collect(),
+        )
+    }
+
+    // Cells shifted to start at the origin and sorted, so shapes equal up to translation compare equal
+    fn normalised(&self) -> Vec<((i32, i32), u8)> {
+        let min_x = self.0.iter().map(|c| c.0 .0).min().unwrap_or(0);
+        let min_y = self.0.iter().map(|c| c.0 .1).min().unwrap_or(0);
+        self.0
+            .iter()
+            .map(|&((x, y), b)| ((x - min_x, y - min_y), b))
+            .sorted()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+    QuarterTurns,
+    EighthTurns,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub eighth_turns: u8,
+    pub reflected: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub position: Coord,
+    pub orientation: Orientation,
+}
+
+pub struct WordSearch {
+    patterns: Vec<Stencil>,
+    symmetry: Symmetry,
+    reflections: bool,
+}
+
+impl WordSearch {
+    pub fn new() -> Self {
+        WordSearch {
+            patterns: Vec::new(),
+            symmetry: Symmetry::None,
+            reflections: false,
+        }
+    }
+
+    pub fn with_word(self, word: &str) -> Self {
+        self.with_stencil(Stencil::word(word))
+    }
+
+    pub fn with_stencil(mut self, stencil: Stencil) -> Self {
+        self.patterns.push(stencil);
+        self
+    }
+
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    pub fn with_reflections(mut self, reflections: bool) -> Self {
+        self.reflections = reflections;
+        self
+    }
+
+    // Every distinct orientation of every pattern, dropping those that only repeat an earlier shape
+    fn variants(&self) -> Vec<(usize, Orientation, Stencil)> {
+        let turns: &[u8] = match self.symmetry {
+            Symmetry::None => &[0],
+            Symmetry::QuarterTurns => &[0, 2, 4, 6],
+            Symmetry::EighthTurns => &[0, 1, 2, 3, 4, 5, 6, 7],
+        };
+        let reflections: &[bool] = if self.reflections {
+            &[false, true]
+        } else {
+            &[false]
+        };
+        let mut variants = Vec::new();
+        for (pattern, stencil) in self.patterns.iter().enumerate() {
+            let mut seen = Vec::new();
+            for &reflected in reflections {
+                for &eighth_turns in turns {
+                    let orientation = Orientation {
+                        eighth_turns,
+                        reflected,
+                    };
+                    let oriented = stencil.oriented(orientation);
+                    let shape = oriented.normalised();
+                    if !seen.contains(&shape) {
+                        seen.push(shape);
+                        variants.push((pattern, orientation, oriented));
+                    }
+                }
+            }
+        }
+        variants
+    }
+
+    // Matches in reading order, `position` being where the pattern's origin lands
+    pub fn find(&self, grid: &Grid<u8>) -> Vec<Match> {
+        let variants = self.variants();
+        grid.coord_iter()
+            .flat_map(|position| {
+                variants
+                    .iter()
+                    .filter(move |(_, _, stencil)| {
+                        stencil.0.iter().all(|&((x, y), b)| {
+                            grid.checked_index(&Coord::new(position.x + x, position.y + y))
+                                == Some(&b)
+                        })
+                    })
+                    .map(move |&(pattern, orientation, _)| Match {
+                        pattern,
+                        position,
+                        orientation,
+                    })
+            })
+            .collect()
+    }
+
+    pub fn count(&self, grid: &Grid<u8>) -> usize {
+        self.find(grid).len()
+    }
+}
+
+impl Default for WordSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[aoc(day4, part1, SEARCH)]
+pub(crate) fn solver_part1_search(input: &Input) -> usize {
+    WordSearch::new()
+        .with_word("XMAS")
+        .with_symmetry(Symmetry::EighthTurns)
+        .count(&parse_byte(input))
+}
+
+#[aoc(day4, part2, SEARCH)]
+pub(crate) fn solver_part2_search(input: &Input) -> usize {
+    WordSearch::new()
+        .with_stencil(Stencil::parse("M.S\n.A.\nM.S"))
+        .with_symmetry(Symmetry::QuarterTurns)
+        .count(&parse_byte(input))
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
+    use super::parse_byte;
     use super::solver_part1;
+    use super::solver_part1_search;
     use super::solver_part2;
+    use super::solver_part2_search;
+    use super::Orientation;
+    use super::Stencil;
+    use super::Symmetry;
+    use super::WordSearch;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "MMMSXXMASM
 MSAMXMSMSA
@@ -279,4 +509,55 @@ MXMXAXMASX";
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 9)
     }
+
+    #[test]
+    fn search() {
+        assert_eq!(solver_part1_search(&input_generator(INPUT)), 18);
+        assert_eq!(solver_part2_search(&input_generator(INPUT)), 9);
+    }
+
+    #[test]
+    fn orientations() {
+        let grid = parse_byte("CAT.\n.A..\nC.T.\nDOGC");
+        let matches = WordSearch::new()
+            .with_word("CAT")
+            .with_word("DOG")
+            .with_symmetry(Symmetry::EighthTurns)
+            .find(&grid);
+        let found = matches
+            .iter()
+            .map(|m| (m.pattern, m.position, m.orientation.eighth_turns))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                (0, Coord::new(0, 0), 0),
+                (0, Coord::new(0, 0), 1),
+                (0, Coord::new(0, 2), 7),
+                (1, Coord::new(0, 3), 0),
+            ]
+        );
+        assert!(matches.iter().all(|m| !m.orientation.reflected));
+    }
+
+    #[test]
+    fn reflections() {
+        // An L tetromino only matches its mirror image once reflections are on
+        let grid = parse_byte(".#\n.#\n##");
+        let stencil = Stencil::parse("#.\n#.\n##");
+        let search = WordSearch::new()
+            .with_stencil(stencil)
+            .with_symmetry(Symmetry::QuarterTurns);
+        assert_eq!(search.count(&grid), 0);
+        let matches = search.with_reflections(true).find(&grid);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            matches[0].orientation,
+            Orientation {
+                eighth_turns: 0,
+                reflected: true
+            }
+        );
+        assert_eq!(matches[0].position, Coord::new(1, 0));
+    }
 }
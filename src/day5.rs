@@ -228,12 +228,12 @@ fn parser_rule_maparray_iter(input: &str) -> (RuleMapArray, Lines) {
 }
 
 #[aoc_generator(day5)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[aoc(day5, part1)]
-fn solver_part1(input: &Input) -> u32 {
+pub(crate) fn solver_part1(input: &Input) -> u32 {
     let (rules, pages) = parser(input);
     pages
         .iter()
@@ -248,7 +248,7 @@ fn solver_part1(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part1, HASH)]
-fn solver_part1_hash(input: &Input) -> u32 {
+pub(crate) fn solver_part1_hash(input: &Input) -> u32 {
     let (rules, pages) = parser_map(input);
 
     pages
@@ -264,7 +264,7 @@ fn solver_part1_hash(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part1, HASH_REDUCED)]
-fn solver_part1_hash_reduced(input: &Input) -> u32 {
+pub(crate) fn solver_part1_hash_reduced(input: &Input) -> u32 {
     let (rules, pages) = parser_map(input);
 
     pages
@@ -280,7 +280,7 @@ fn solver_part1_hash_reduced(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part1, FAST_HASH_REDUCED)]
-fn solver_part1_fast_hash_reduced(input: &Input) -> u32 {
+pub(crate) fn solver_part1_fast_hash_reduced(input: &Input) -> u32 {
     let (rules, pages) = parser_rule_map(input);
 
     pages
@@ -341,7 +341,7 @@ fn page_line_sorted(line: &str, rules: &RuleMap) -> (bool, Vec<u8>) {
 }
 
 #[aoc(day5, part1, FAST_HASH_REDUCED_INLINE)]
-fn solver_part1_fast_hash_reduced_line(input: &Input) -> u32 {
+pub(crate) fn solver_part1_fast_hash_reduced_line(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_map_iter(input);
 
     lines
@@ -357,7 +357,7 @@ fn solver_part1_fast_hash_reduced_line(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part1, FAST_HASH_INLINE_SORT)]
-fn solver_part1_fast_hash_inline_sort(input: &Input) -> u32 {
+pub(crate) fn solver_part1_fast_hash_inline_sort(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_map_iter(input);
 
     lines
@@ -373,7 +373,7 @@ fn solver_part1_fast_hash_inline_sort(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part1, FAST_HASH_SET_INLINE_SORT)]
-fn solver_part1_fast_hash_set_inline_sort(input: &Input) -> u32 {
+pub(crate) fn solver_part1_fast_hash_set_inline_sort(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_mapset_iter(input);
 
     lines
@@ -390,7 +390,7 @@ fn solver_part1_fast_hash_set_inline_sort(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part1, FAST_HASH_ARRAY_INLINE_SORT)]
-fn solver_part1_fast_hash_array_inline_sort(input: &Input) -> u32 {
+pub(crate) fn solver_part1_fast_hash_array_inline_sort(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_maparray_iter(input);
 
     lines
@@ -407,7 +407,7 @@ fn solver_part1_fast_hash_array_inline_sort(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part2)]
-fn solver_part2(input: &Input) -> u32 {
+pub(crate) fn solver_part2(input: &Input) -> u32 {
     let (rules, pages) = parser(input);
     pages
         .iter()
@@ -433,7 +433,7 @@ fn solver_part2(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part2, HASH)]
-fn solver_part2_hash(input: &Input) -> u32 {
+pub(crate) fn solver_part2_hash(input: &Input) -> u32 {
     let (rules, pages) = parser_map(input);
 
     pages
@@ -480,7 +480,7 @@ fn sort_pages(pages: &[u8], rules: &RuleMap) -> Vec<u8> {
 }
 
 #[aoc(day5, part2, FAST_HASH_INLINE)]
-fn solver_part2_fast_hash_inline(input: &Input) -> u32 {
+pub(crate) fn solver_part2_fast_hash_inline(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_map_iter(input);
 
     lines
@@ -502,7 +502,7 @@ fn solver_part2_fast_hash_inline(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part2, FAST_HASH_INLINE_SORT)]
-fn solver_part2_fast_hash_inline_sort(input: &Input) -> u32 {
+pub(crate) fn solver_part2_fast_hash_inline_sort(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_map_iter(input);
 
     lines
@@ -529,7 +529,7 @@ fn solver_part2_fast_hash_inline_sort(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part2, FAST_HASH_INLINE_SORT_NTH)]
-fn solver_part2_fast_hash_inline_sort_nth(input: &Input) -> u32 {
+pub(crate) fn solver_part2_fast_hash_inline_sort_nth(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_map_iter(input);
 
     lines
@@ -558,7 +558,7 @@ fn solver_part2_fast_hash_inline_sort_nth(input: &Input) -> u32 {
 }
 
 #[aoc(day5, part2, FAST_HASH_ARRAY_INLINE_SORT_NTH)]
-fn solver_part2_fast_hash_array_inline_sort_nth(input: &Input) -> u32 {
+pub(crate) fn solver_part2_fast_hash_array_inline_sort_nth(input: &Input) -> u32 {
     let (rules, lines) = parser_rule_maparray_iter(input);
 
     lines
@@ -585,13 +585,128 @@ fn solver_part2_fast_hash_array_inline_sort_nth(input: &Input) -> u32 {
         .sum()
 }
 
+// Pages that the rules for an update require to come before each other in a loop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleCycle(pub Vec<u64>);
+
+// Ordering rules for page IDs of any size
+#[derive(Debug, Default)]
+pub struct RuleEngine(HashMap<u64, HashSet<u64>>);
+
+impl RuleEngine {
+    pub fn new() -> Self {
+        RuleEngine::default()
+    }
+
+    pub fn add_rule(&mut self, before: u64, after: u64) {
+        self.0.entry(before).or_default().insert(after);
+    }
+
+    pub fn parse(input: &str) -> (RuleEngine, Vec<Vec<u64>>) {
+        let mut rules = RuleEngine::new();
+        let mut lines = input.lines();
+        for l in lines.take_while_ref(|l| !l.is_empty()) {
+            let (before, after) = l.split_once('|').unwrap();
+            rules.add_rule(before.parse().unwrap(), after.parse().unwrap());
+        }
+        lines.next();
+        let updates = lines
+            .map(|l| l.split(',').map(|p| p.parse().unwrap()).collect())
+            .collect();
+        (rules, updates)
+    }
+
+    fn must_precede(&self, before: &u64, after: &u64) -> bool {
+        self.0.get(before).is_some_and(|set| set.contains(after))
+    }
+
+    pub fn is_ordered(&self, update: &[u64]) -> bool {
+        update.iter().enumerate().all(|(ind, page)| {
+            !update[..ind]
+                .iter()
+                .any(|prev| self.must_precede(page, prev))
+        })
+    }
+
+    // Topological sort using only the rules between pages in the update, keeping the
+    // original order where the rules allow it. Pages must not repeat within an update.
+    pub fn correct(&self, update: &[u64]) -> Result<Vec<u64>, RuleCycle> {
+        let mut incoming = update
+            .iter()
+            .map(|page| update.iter().filter(|p| self.must_precede(p, page)).count())
+            .collect_vec();
+        let mut placed = vec![false; update.len()];
+        let mut sorted = Vec::with_capacity(update.len());
+        while sorted.len() < update.len() {
+            let Some(next) = (0..update.len()).find(|&i| !placed[i] && incoming[i] == 0) else {
+                return Err(self.find_cycle(update, &placed));
+            };
+            placed[next] = true;
+            sorted.push(update[next]);
+            for (i, page) in update.iter().enumerate() {
+                if !placed[i] && self.must_precede(&update[next], page) {
+                    incoming[i] -= 1;
+                }
+            }
+        }
+        Ok(sorted)
+    }
+
+    // Every unplaced page has an unplaced predecessor, so walking back must repeat a page
+    fn find_cycle(&self, update: &[u64], placed: &[bool]) -> RuleCycle {
+        let mut current = (0..update.len()).find(|&i| !placed[i]).unwrap();
+        let mut walked = vec![current];
+        loop {
+            current = (0..update.len())
+                .find(|&i| !placed[i] && self.must_precede(&update[i], &update[current]))
+                .unwrap();
+            if let Some(start) = walked.iter().position(|&i| i == current) {
+                let mut cycle = walked.split_off(start);
+                cycle.reverse();
+                // Start from the page that comes first in the update
+                let first = cycle.iter().position_min().unwrap();
+                cycle.rotate_left(first);
+                return RuleCycle(cycle.into_iter().map(|i| update[i]).collect());
+            }
+            walked.push(current);
+        }
+    }
+}
+
+#[aoc(day5, part1, RULE_ENGINE)]
+pub(crate) fn solver_part1_rule_engine(input: &Input) -> u64 {
+    let (rules, updates) = RuleEngine::parse(input);
+    updates
+        .iter()
+        .filter(|update| rules.is_ordered(update))
+        .map(|update| update[update.len() / 2])
+        .sum()
+}
+
+#[aoc(day5, part2, RULE_ENGINE)]
+pub(crate) fn solver_part2_rule_engine(input: &Input) -> u64 {
+    let (rules, updates) = RuleEngine::parse(input);
+    updates
+        .iter()
+        .filter(|update| !rules.is_ordered(update))
+        .map(|update| {
+            let sorted = rules.correct(update).unwrap();
+            sorted[sorted.len() / 2]
+        })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day5::solver_part2_fast_hash_inline;
 
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_rule_engine;
     use super::solver_part2;
+    use super::solver_part2_rule_engine;
+    use super::RuleCycle;
+    use super::RuleEngine;
 
     static INPUT: &str = "47|53
 97|13
@@ -636,4 +751,39 @@ mod tests {
     fn part2_2() {
         assert_eq!(solver_part2_fast_hash_inline(&input_generator(INPUT)), 123)
     }
+
+    #[test]
+    fn part1_rule_engine() {
+        assert_eq!(solver_part1_rule_engine(&input_generator(INPUT)), 143)
+    }
+
+    #[test]
+    fn part2_rule_engine() {
+        assert_eq!(solver_part2_rule_engine(&input_generator(INPUT)), 123)
+    }
+
+    #[test]
+    fn correct() {
+        let (rules, _) = RuleEngine::parse(INPUT);
+        assert_eq!(
+            rules.correct(&[97, 13, 75, 29, 47]),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+        assert_eq!(rules.correct(&[75, 29, 13]), Ok(vec![75, 29, 13]));
+    }
+
+    #[test]
+    fn cycle() {
+        let mut rules = RuleEngine::new();
+        rules.add_rule(1000, 7);
+        rules.add_rule(7, 123456);
+        rules.add_rule(123456, 1000);
+        rules.add_rule(5, 7);
+        assert_eq!(
+            rules.correct(&[5, 7, 1000, 123456]),
+            Err(RuleCycle(vec![7, 123456, 1000]))
+        );
+        // The rule closing the loop only matters when its pages are in the update
+        assert_eq!(rules.correct(&[7, 1000, 5]), Ok(vec![1000, 5, 7]));
+    }
 }
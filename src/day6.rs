@@ -10,7 +10,7 @@ use rayon::prelude::*;
 type Input = Grid<Square>;
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Square {
+pub enum Square {
     Empty,
     Visited,
     Obstruction,
@@ -18,14 +18,34 @@ enum Square {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
     Right,
 }
+
+impl Direction {
+    fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    fn forward(self, coord: Coord) -> Coord {
+        match self {
+            Direction::Up => coord.north(),
+            Direction::Right => coord.east(),
+            Direction::Down => coord.south(),
+            Direction::Left => coord.west(),
+        }
+    }
+}
 #[aoc_generator(day6)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     let width = input.lines().next().unwrap().len();
     Grid::from_iter(
         &mut input.lines().flat_map(|l| {
@@ -121,7 +141,7 @@ fn run_grid_tracked(
 }
 
 #[aoc(day6, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut grid = input.clone();
     let mut current = Coord::new(0, 0);
     let mut direction = Direction::Up;
@@ -179,7 +199,7 @@ fn is_loop(grid: &mut Grid<Square>, mut current: Coord, mut direction: Direction
     }
 }
 #[aoc(day6, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let mut grid = input.clone();
     let mut start = Coord::new(0, 0);
     let mut start_direction = Direction::Up;
@@ -209,7 +229,7 @@ fn solver_part2(input: &Input) -> usize {
 }
 
 #[aoc(day6, part2, VISITED)]
-fn solver_part2_visited(input: &Input) -> usize {
+pub(crate) fn solver_part2_visited(input: &Input) -> usize {
     let mut grid = input.clone();
     let mut start = Coord::new(0, 0);
     let mut start_direction = Direction::Up;
@@ -244,7 +264,7 @@ fn solver_part2_visited(input: &Input) -> usize {
 }
 
 #[aoc(day6, part2, VISITED_PARALLEL)]
-fn solver_part2_parallel(input: &Input) -> usize {
+pub(crate) fn solver_part2_parallel(input: &Input) -> usize {
     let mut grid = input.clone();
     let mut start = Coord::new(0, 0);
     let mut start_direction = Direction::Up;
@@ -285,7 +305,7 @@ fn previous_coord(coord: &Coord, direction: &Direction) -> Coord {
     }
 }
 #[aoc(day6, part2, VISITED_PARALLEl_SHORT)]
-fn solver_part2_short(input: &Input) -> usize {
+pub(crate) fn solver_part2_short(input: &Input) -> usize {
     let mut grid = input.clone();
     let mut start = Coord::new(0, 0);
     let mut start_direction = Direction::Up;
@@ -317,11 +337,110 @@ fn solver_part2_short(input: &Input) -> usize {
         })
         .count()
 }
+
+// Yields every (position, facing) of the guard, including turns on the spot, until they leave
+// the map. Never ends if the guard is stuck in a loop.
+pub struct Patrol<'a> {
+    grid: &'a Grid<Square>,
+    obstruction: Option<Coord>,
+    state: Option<(Coord, Direction)>,
+}
+
+impl<'a> Patrol<'a> {
+    pub fn new(grid: &'a Grid<Square>) -> Patrol<'a> {
+        let state = grid.iter().find_map(|(coord, sq)| match sq {
+            Square::Guard(d) => Some((coord, *d)),
+            _ => None,
+        });
+        Patrol {
+            grid,
+            obstruction: None,
+            state,
+        }
+    }
+
+    // Patrol with one extra obstruction added to the map
+    pub fn with_obstruction(mut self, coord: Coord) -> Patrol<'a> {
+        self.obstruction = Some(coord);
+        self
+    }
+
+    // The repeating part of the patrol, or None if the guard leaves the map
+    pub fn find_loop(self) -> Option<Vec<(Coord, Direction)>> {
+        let mut seen = HashMap::new();
+        let mut states = Vec::new();
+        for state in self {
+            if let Some(&start) = seen.get(&state) {
+                return Some(states.split_off(start));
+            }
+            seen.insert(state, states.len());
+            states.push(state);
+        }
+        None
+    }
+}
+
+impl Iterator for Patrol<'_> {
+    type Item = (Coord, Direction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (current, direction) = self.state?;
+        let ahead = direction.forward(current);
+        self.state = match self.grid.checked_index(&ahead) {
+            None => None,
+            Some(Square::Obstruction) => Some((current, direction.turn_right())),
+            Some(_) if self.obstruction == Some(ahead) => Some((current, direction.turn_right())),
+            Some(_) => Some((ahead, direction)),
+        };
+        Some((current, direction))
+    }
+}
+
+// Every position where a new obstruction traps the guard, with the loop it causes, in the
+// order the guard would first reach them
+pub fn loop_obstructions(grid: &Grid<Square>) -> Vec<(Coord, Vec<(Coord, Direction)>)> {
+    let patrol = Patrol::new(grid);
+    let start = patrol.state.map(|(coord, _)| coord);
+    let mut seen = HashSet::new();
+    let candidates: Vec<Coord> = patrol
+        .map(|(coord, _)| coord)
+        .filter(|coord| Some(*coord) != start && seen.insert(*coord))
+        .collect();
+    candidates
+        .into_par_iter()
+        .filter_map(|coord| {
+            Patrol::new(grid)
+                .with_obstruction(coord)
+                .find_loop()
+                .map(|cycle| (coord, cycle))
+        })
+        .collect()
+}
+
+#[aoc(day6, part1, PATROL)]
+pub(crate) fn solver_part1_patrol(input: &Input) -> usize {
+    Patrol::new(input)
+        .map(|(coord, _)| coord)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+#[aoc(day6, part2, PATROL)]
+pub(crate) fn solver_part2_patrol(input: &Input) -> usize {
+    loop_obstructions(input).len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
+    use super::loop_obstructions;
     use super::solver_part1;
+    use super::solver_part1_patrol;
     use super::solver_part2;
+    use super::solver_part2_patrol;
+    use super::Direction;
+    use super::Patrol;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "....#.....
 .........#
@@ -343,6 +462,48 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 6)
     }
+
+    #[test]
+    fn part1_patrol() {
+        assert_eq!(solver_part1_patrol(&input_generator(INPUT)), 41)
+    }
+
+    #[test]
+    fn part2_patrol() {
+        assert_eq!(solver_part2_patrol(&input_generator(INPUT)), 6)
+    }
+
+    #[test]
+    fn patrol() {
+        let grid = input_generator(INPUT);
+        let mut patrol = Patrol::new(&grid);
+        assert_eq!(patrol.next(), Some((Coord::new(4, 6), Direction::Up)));
+        assert_eq!(patrol.nth(4), Some((Coord::new(4, 1), Direction::Up)));
+        assert_eq!(patrol.next(), Some((Coord::new(4, 1), Direction::Right)));
+        assert_eq!(patrol.last(), Some((Coord::new(7, 9), Direction::Down)));
+        assert_eq!(Patrol::new(&grid).find_loop(), None);
+    }
+
+    #[test]
+    fn obstructions() {
+        let grid = input_generator(INPUT);
+        let obstructions = loop_obstructions(&grid);
+        assert_eq!(
+            obstructions.iter().map(|(c, _)| *c).collect::<Vec<_>>(),
+            vec![
+                Coord::new(3, 6),
+                Coord::new(6, 7),
+                Coord::new(3, 8),
+                Coord::new(1, 8),
+                Coord::new(7, 7),
+                Coord::new(7, 9)
+            ]
+        );
+        // Obstruction next to the start sends the guard round a rectangle, 18 steps and 4 turns
+        let cycle = &obstructions[0].1;
+        assert_eq!(cycle.len(), 22);
+        assert!(cycle.contains(&(Coord::new(4, 6), Direction::Left)));
+    }
 }
 
 // AOC 2024
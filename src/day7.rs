@@ -32,12 +32,12 @@ fn op2_iter(len: usize) -> itertools::MultiProduct<std::slice::Iter<'static, Op2
 }
 
 #[aoc_generator(day7)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[aoc(day7, part1)]
-fn solver_part1(input: &Input) -> u64 {
+pub(crate) fn solver_part1(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -63,7 +63,7 @@ fn solver_part1(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part1, EARLY_EXIT)]
-fn solver_part1_early_exit(input: &Input) -> u64 {
+pub(crate) fn solver_part1_early_exit(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -155,7 +155,7 @@ fn recursive_2(total: u64, index: usize, vec: &[u64]) -> bool {
     }
 }
 #[aoc(day7, part1, RECURSIVE)]
-fn solver_part1_recursive(input: &Input) -> u64 {
+pub(crate) fn solver_part1_recursive(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -173,7 +173,7 @@ fn solver_part1_recursive(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part1, RECURSIVE_PARALLEL)]
-fn solver_part1_recursive_paralell(input: &Input) -> u64 {
+pub(crate) fn solver_part1_recursive_paralell(input: &Input) -> u64 {
     input
         .lines()
         .par_bridge()
@@ -191,7 +191,7 @@ fn solver_part1_recursive_paralell(input: &Input) -> u64 {
         .sum()
 }
 #[aoc(day7, part1, RECURSIVE_SWITCH)]
-fn solver_part1_recursive_switch(input: &Input) -> u64 {
+pub(crate) fn solver_part1_recursive_switch(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -209,7 +209,7 @@ fn solver_part1_recursive_switch(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part2)]
-fn solver_part2(input: &Input) -> u64 {
+pub(crate) fn solver_part2(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -236,7 +236,7 @@ fn solver_part2(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part2, FAST_CONCAT)]
-fn solver_part2_fast_concat(input: &Input) -> u64 {
+pub(crate) fn solver_part2_fast_concat(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -263,7 +263,7 @@ fn solver_part2_fast_concat(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part2, FASTER_CONCAT)]
-fn solver_part2_faster_concat(input: &Input) -> u64 {
+pub(crate) fn solver_part2_faster_concat(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -300,7 +300,7 @@ fn solver_part2_faster_concat(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part2, FASTER_CONCAT_EARLY_EXIT)]
-fn solver_part2_faster_concat_early_exit(input: &Input) -> u64 {
+pub(crate) fn solver_part2_faster_concat_early_exit(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -346,7 +346,7 @@ fn solver_part2_faster_concat_early_exit(input: &Input) -> u64 {
 }
 
 #[aoc(day7, part2, RECURSIVE)]
-fn solver_part2_recursive(input: &Input) -> u64 {
+pub(crate) fn solver_part2_recursive(input: &Input) -> u64 {
     input
         .lines()
         .filter_map(|l| {
@@ -363,11 +363,191 @@ fn solver_part2_recursive(input: &Input) -> u64 {
         .sum()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Concatenate,
+    Subtract,
+    Divide,
+}
+
+impl Operator {
+    // None when the result would leave i128 or the operator cannot be applied.
+    // Totals may go negative part way through, such as 1 - 3 + 7
+    pub fn apply(&self, total: i128, v: i128) -> Option<i128> {
+        match self {
+            Operator::Add => total.checked_add(v),
+            Operator::Multiply => total.checked_mul(v),
+            Operator::Concatenate => {
+                if v < 0 {
+                    return None;
+                }
+                let digits = if v == 0 { 1 } else { v.ilog10() + 1 };
+                let shifted = total.checked_mul(10i128.checked_pow(digits)?)?;
+                // Appending digits to "-2" gives "-25"
+                if total < 0 {
+                    shifted.checked_sub(v)
+                } else {
+                    shifted.checked_add(v)
+                }
+            }
+            Operator::Subtract => total.checked_sub(v),
+            Operator::Divide => total.checked_div(v),
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Multiply => "*",
+            Operator::Concatenate => "||",
+            Operator::Subtract => "-",
+            Operator::Divide => "/",
+        }
+    }
+
+    // True if applying this never makes a non negative running total smaller, given a positive value
+    fn is_growing(&self) -> bool {
+        matches!(
+            self,
+            Operator::Add | Operator::Multiply | Operator::Concatenate
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Equation {
+    pub target: i128,
+    pub values: Vec<i128>,
+}
+
+impl Equation {
+    pub fn parse(line: &str) -> Equation {
+        let (target, values) = line.split_once(": ").unwrap();
+        Equation {
+            target: target.parse().unwrap(),
+            values: values.split(' ').map(|v| v.parse().unwrap()).collect(),
+        }
+    }
+
+    // Written out left to right, such as "3267 = 81 + 40 * 27"
+    pub fn render(&self, ops: &[Operator]) -> String {
+        let mut str = format!("{} = {}", self.target, self.values[0]);
+        for (op, v) in ops.iter().zip(&self.values[1..]) {
+            str += &format!(" {} {}", op.symbol(), v);
+        }
+        str
+    }
+}
+
+// Searches for operators that make an equation true, evaluating left to right
+pub struct OperatorSearch {
+    ops: Vec<Operator>,
+}
+
+impl OperatorSearch {
+    pub fn new(ops: &[Operator]) -> OperatorSearch {
+        OperatorSearch { ops: ops.to_vec() }
+    }
+
+    pub fn find(&self, equation: &Equation) -> Option<Vec<Operator>> {
+        let mut found = Vec::new();
+        self.search(
+            equation,
+            equation.values[0],
+            &mut Vec::new(),
+            &mut found,
+            false,
+        );
+        found.pop()
+    }
+
+    pub fn find_all(&self, equation: &Equation) -> Vec<Vec<Operator>> {
+        let mut found = Vec::new();
+        self.search(
+            equation,
+            equation.values[0],
+            &mut Vec::new(),
+            &mut found,
+            true,
+        );
+        found
+    }
+
+    // Returns true once the search can stop
+    fn search(
+        &self,
+        equation: &Equation,
+        total: i128,
+        current: &mut Vec<Operator>,
+        found: &mut Vec<Vec<Operator>>,
+        all: bool,
+    ) -> bool {
+        let index = current.len() + 1;
+        if index == equation.values.len() {
+            if total == equation.target {
+                found.push(current.clone());
+                return !all;
+            }
+            return false;
+        }
+        let v = equation.values[index];
+        for op in &self.ops {
+            let Some(next) = op.apply(total, v) else {
+                continue;
+            };
+            // Totals only grow from here, so overshooting can never come back
+            if next > equation.target
+                && next >= 0
+                && self.ops.iter().all(Operator::is_growing)
+                && equation.values[index..].iter().all(|&v| v > 0)
+            {
+                continue;
+            }
+            current.push(*op);
+            let done = self.search(equation, next, current, found, all);
+            current.pop();
+            if done {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[aoc(day7, part1, OPERATOR_SEARCH)]
+pub(crate) fn solver_part1_operator_search(input: &Input) -> i128 {
+    let search = OperatorSearch::new(&[Operator::Add, Operator::Multiply]);
+    input
+        .lines()
+        .map(Equation::parse)
+        .filter(|eq| search.find(eq).is_some())
+        .map(|eq| eq.target)
+        .sum()
+}
+
+#[aoc(day7, part2, OPERATOR_SEARCH)]
+pub(crate) fn solver_part2_operator_search(input: &Input) -> i128 {
+    let search = OperatorSearch::new(&[Operator::Add, Operator::Multiply, Operator::Concatenate]);
+    input
+        .lines()
+        .map(Equation::parse)
+        .filter(|eq| search.find(eq).is_some())
+        .map(|eq| eq.target)
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_operator_search;
     use super::solver_part2;
+    use super::solver_part2_operator_search;
+    use super::Equation;
+    use super::Operator;
+    use super::OperatorSearch;
 
     static INPUT: &str = "190: 10 19
 3267: 81 40 27
@@ -388,4 +568,61 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 11387)
     }
+
+    #[test]
+    fn part1_operator_search() {
+        assert_eq!(solver_part1_operator_search(&input_generator(INPUT)), 3749)
+    }
+
+    #[test]
+    fn part2_operator_search() {
+        assert_eq!(solver_part2_operator_search(&input_generator(INPUT)), 11387)
+    }
+
+    #[test]
+    fn expressions() {
+        let search = OperatorSearch::new(&[Operator::Add, Operator::Multiply]);
+        let eq = Equation::parse("3267: 81 40 27");
+        assert_eq!(
+            search.find_all(&eq),
+            vec![
+                vec![Operator::Add, Operator::Multiply],
+                vec![Operator::Multiply, Operator::Add]
+            ]
+        );
+        let ops = search.find(&eq).unwrap();
+        assert_eq!(eq.render(&ops), "3267 = 81 + 40 * 27");
+        assert_eq!(search.find(&Equation::parse("83: 17 5")), None);
+    }
+
+    #[test]
+    fn extra_operators() {
+        let search = OperatorSearch::new(&[Operator::Subtract, Operator::Divide]);
+        let eq = Equation::parse("3: 20 5 1");
+        assert_eq!(
+            search.find(&eq),
+            Some(vec![Operator::Divide, Operator::Subtract])
+        );
+        let search = OperatorSearch::new(&[Operator::Concatenate, Operator::Multiply]);
+        let eq = Equation::parse("184467440737095516160000: 18446744073709551616 10 1000");
+        assert_eq!(
+            search.find_all(&eq),
+            vec![vec![Operator::Multiply, Operator::Multiply]]
+        );
+    }
+
+    #[test]
+    fn negative_totals() {
+        // 1 - 3 goes below zero before + 7 brings it back
+        let search = OperatorSearch::new(&[Operator::Add, Operator::Subtract]);
+        assert_eq!(
+            search.find_all(&Equation::parse("5: 1 3 7")),
+            vec![vec![Operator::Subtract, Operator::Add]]
+        );
+        let search = OperatorSearch::new(&[Operator::Subtract, Operator::Concatenate]);
+        assert_eq!(
+            search.find(&Equation::parse("-25: 1 3 5")),
+            Some(vec![Operator::Subtract, Operator::Concatenate])
+        );
+    }
 }
@@ -1,19 +1,24 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
+use aoc_tools::grid::Coord;
+use aoc_tools::grid::Grid;
+use fxhash::FxHashSet;
 use itertools::Itertools;
+use num::Integer;
 
 type Input = String;
 
 #[aoc_generator(day8)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
 #[aoc(day8, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut map: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
     let mut total = 0;
     let mut length = 0;
@@ -56,7 +61,7 @@ fn solver_part1(input: &Input) -> usize {
 }
 
 #[aoc(day8, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let mut map: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
     let mut total = 0;
     let mut length = 0;
@@ -114,11 +119,150 @@ fn solver_part2(input: &Input) -> usize {
     set.len()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Harmonics {
+    // Twice as far from one antenna as the other, on the outside of the pair
+    First,
+    // Every grid point in line with the pair
+    Collinear,
+    // Any point in line where one antenna is `n` times as far as the other, inside or outside
+    Ratio(u32),
+}
+
+pub struct Antennas {
+    grid: Grid<u8>,
+    frequencies: BTreeMap<u8, Vec<Coord>>,
+}
+
+impl Antennas {
+    // Short lines are padded out with '.' to the longest one
+    pub fn parse(input: &str) -> Self {
+        let width = input.lines().map(|l| l.len()).max().unwrap_or(0);
+        let height = input.lines().count();
+        let mut grid = Grid::new(b'.', width, height);
+        let mut frequencies: BTreeMap<u8, Vec<Coord>> = BTreeMap::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, b) in line.bytes().enumerate() {
+                let c = Coord::new(x as i32, y as i32);
+                grid[c] = b;
+                if b != b'.' {
+                    frequencies.entry(b).or_default().push(c);
+                }
+            }
+        }
+        Antennas { grid, frequencies }
+    }
+
+    pub fn frequencies(&self) -> impl Iterator<Item = u8> + '_ {
+        self.frequencies.keys().copied()
+    }
+
+    fn in_bounds(&self, c: Coord) -> bool {
+        self.grid.checked_index(&c).is_some()
+    }
+
+    // Points a + (b - a) * num / den that land on the grid
+    fn fractions(&self, a: Coord, b: Coord, fractions: &[(i32, i32)], out: &mut Vec<Coord>) {
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        for &(num, den) in fractions {
+            if den == 0 || (dx * num) % den != 0 || (dy * num) % den != 0 {
+                continue;
+            }
+            let c = Coord::new(a.x + dx * num / den, a.y + dy * num / den);
+            if self.in_bounds(c) {
+                out.push(c);
+            }
+        }
+    }
+
+    fn pair_antinodes(&self, a: Coord, b: Coord, harmonics: Harmonics, out: &mut Vec<Coord>) {
+        match harmonics {
+            Harmonics::First => self.fractions(a, b, &[(2, 1), (-1, 1)], out),
+            Harmonics::Collinear => {
+                let g = (b.x - a.x).gcd(&(b.y - a.y));
+                let (dx, dy) = ((b.x - a.x) / g, (b.y - a.y) / g);
+                for sign in [1, -1] {
+                    let mut c = a;
+                    while self.in_bounds(c) {
+                        out.push(c);
+                        c = Coord::new(c.x + sign * dx, c.y + sign * dy);
+                    }
+                }
+            }
+            Harmonics::Ratio(n) => {
+                let n = n as i32;
+                self.fractions(
+                    a,
+                    b,
+                    &[(n, n + 1), (1, n + 1), (n, n - 1), (-1, n - 1)],
+                    out,
+                )
+            }
+        }
+    }
+
+    // Antinodes of each frequency in reading order. The same spot can appear under several frequencies
+    pub fn antinodes(&self, harmonics: Harmonics) -> BTreeMap<u8, Vec<Coord>> {
+        self.frequencies
+            .iter()
+            .map(|(&frequency, antennas)| {
+                let mut out = Vec::new();
+                for (&a, &b) in antennas.iter().tuple_combinations() {
+                    self.pair_antinodes(a, b, harmonics, &mut out);
+                }
+                out.sort_by_key(|c| (c.y, c.x));
+                out.dedup();
+                (frequency, out)
+            })
+            .collect()
+    }
+
+    pub fn unique_antinodes(&self, harmonics: Harmonics) -> FxHashSet<Coord> {
+        self.antinodes(harmonics).into_values().flatten().collect()
+    }
+
+    // The map with '#' on empty cells holding an antinode
+    pub fn render(&self, harmonics: Harmonics) -> String {
+        let antinodes = self.unique_antinodes(harmonics);
+        let mut out = String::new();
+        for y in 0..self.grid.height as i32 {
+            for x in 0..self.grid.width as i32 {
+                let c = Coord::new(x, y);
+                out.push(match self.grid[c] {
+                    b'.' if antinodes.contains(&c) => '#',
+                    b => b as char,
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[aoc(day8, part1, ENGINE)]
+pub(crate) fn solver_part1_engine(input: &Input) -> usize {
+    Antennas::parse(input)
+        .unique_antinodes(Harmonics::First)
+        .len()
+}
+
+#[aoc(day8, part2, ENGINE)]
+pub(crate) fn solver_part2_engine(input: &Input) -> usize {
+    Antennas::parse(input)
+        .unique_antinodes(Harmonics::Collinear)
+        .len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_engine;
     use super::solver_part2;
+    use super::solver_part2_engine;
+    use super::Antennas;
+    use super::Harmonics;
+    use aoc_tools::grid::Coord;
 
     static INPUT: &str = "............
 ........0...
@@ -142,4 +286,57 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 34)
     }
+
+    #[test]
+    fn engine() {
+        assert_eq!(solver_part1_engine(&input_generator(INPUT)), 14);
+        assert_eq!(solver_part2_engine(&input_generator(INPUT)), 34);
+
+        let antennas = Antennas::parse(INPUT);
+        assert!(antennas.frequencies().eq([b'0', b'A']));
+        let grouped = antennas.antinodes(Harmonics::First);
+        assert_eq!(grouped[&b'0'].len(), 10);
+        assert_eq!(grouped[&b'A'].len(), 5);
+        assert_eq!(
+            antennas.render(Harmonics::First),
+            "\
+......#....#
+...#....0...
+....#0....#.
+..#....0....
+....0....#..
+.#....A.....
+...#........
+#......#....
+........A...
+.........A..
+..........#.
+..........#.
+"
+        );
+    }
+
+    #[test]
+    fn harmonics() {
+        // Ragged lines are padded to the longest
+        let antennas = Antennas::parse("..........\n\n\na\n\n\n\n\n..a\n..........");
+        assert_eq!(
+            antennas.antinodes(Harmonics::Collinear)[&b'a'],
+            [Coord::new(0, 3), Coord::new(2, 8)]
+        );
+        let antennas = Antennas::parse(
+            "..........\n..........\n..........\n...a\n\n\n......a\n\n\n..........",
+        );
+        assert_eq!(
+            antennas.antinodes(Harmonics::Ratio(2))[&b'a'],
+            [
+                Coord::new(0, 0),
+                Coord::new(4, 4),
+                Coord::new(5, 5),
+                Coord::new(9, 9)
+            ]
+        );
+        assert_eq!(antennas.antinodes(Harmonics::Collinear)[&b'a'].len(), 10);
+        assert_eq!(antennas.antinodes(Harmonics::Ratio(1))[&b'a'], []);
+    }
 }
@@ -2,6 +2,8 @@
 use aoc_runner_derive::aoc;
 use aoc_runner_derive::aoc_generator;
 use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
 type Input = String;
 
@@ -27,7 +29,7 @@ enum Chunk {
 }
 
 #[aoc_generator(day9)]
-fn input_generator(input: &str) -> Input {
+pub(crate) fn input_generator(input: &str) -> Input {
     input.to_string()
 }
 
@@ -81,7 +83,7 @@ fn checksum_blocks(fs: &[Block]) -> usize {
 }
 
 #[aoc(day9, part1)]
-fn solver_part1(input: &Input) -> usize {
+pub(crate) fn solver_part1(input: &Input) -> usize {
     let mut fs = create_blocks(input);
     swap_blocks(&mut fs);
     checksum_blocks(&fs)
@@ -94,7 +96,7 @@ fn _next_tail(tail: &mut usize, fs: &[Block]) {
     }
 }
 #[aoc(day9, part1, INLINE)]
-fn solver_part1_inline(input: &Input) -> usize {
+pub(crate) fn solver_part1_inline(input: &Input) -> usize {
     let fs = input.chars().map(|c| c as u8 - 48).collect_vec();
     let mut head = 0;
     let mut tail = if fs.len() % 2 == 0 {
@@ -268,26 +270,189 @@ fn checksum_chunks(fs: &[Chunk]) -> usize {
         .sum()
 }
 #[aoc(day9, part2)]
-fn solver_part2(input: &Input) -> usize {
+pub(crate) fn solver_part2(input: &Input) -> usize {
     let mut fs = create_chunks(input);
     rearrange_chunks(&mut fs);
     checksum_chunks(&fs)
 }
 
 #[aoc(day9, part2, SHORT)]
-fn solver_part2_short(input: &Input) -> usize {
+pub(crate) fn solver_part2_short(input: &Input) -> usize {
     let mut fs = create_chunks(input);
     rearrange_chunks_short(&mut fs);
     checksum_chunks(&fs)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub id: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+// Disk layout as file segments, a file is split into several after block compaction
+#[derive(Debug, Clone)]
+pub struct Disk {
+    segments: Vec<Segment>,
+    size: usize,
+}
+
+impl Disk {
+    pub fn parse(map: &str) -> Disk {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for (i, c) in map.trim().bytes().enumerate() {
+            let len = (c - b'0') as usize;
+            if i % 2 == 0 && len > 0 {
+                segments.push(Segment {
+                    id: i / 2,
+                    start,
+                    len,
+                });
+            }
+            start += len;
+        }
+        Disk {
+            segments,
+            size: start,
+        }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    fn blocks(&self) -> Vec<Option<usize>> {
+        let mut blocks = vec![None; self.size];
+        for seg in &self.segments {
+            blocks[seg.start..seg.start + seg.len].fill(Some(seg.id));
+        }
+        blocks
+    }
+
+    // Free spans as (start, len) in disk order
+    fn free_spans(&self) -> Vec<(usize, usize)> {
+        let mut segments = self.segments.clone();
+        segments.sort_by_key(|seg| seg.start);
+        let mut spans = Vec::new();
+        let mut pos = 0;
+        for seg in segments {
+            if seg.start > pos {
+                spans.push((pos, seg.start - pos));
+            }
+            pos = seg.start + seg.len;
+        }
+        if self.size > pos {
+            spans.push((pos, self.size - pos));
+        }
+        spans
+    }
+
+    // Moves single blocks from the end into the first free block
+    pub fn compact_blocks(&mut self) {
+        let mut blocks = self.blocks();
+        let mut head = 0;
+        let mut tail = blocks.len();
+        loop {
+            while head < tail && blocks[head].is_some() {
+                head += 1;
+            }
+            while tail > head && blocks[tail - 1].is_none() {
+                tail -= 1;
+            }
+            if tail <= head + 1 {
+                break;
+            }
+            blocks.swap(head, tail - 1);
+        }
+        self.segments = blocks
+            .iter()
+            .enumerate()
+            .chunk_by(|(_, id)| **id)
+            .into_iter()
+            .filter_map(|(id, mut group)| {
+                let (start, _) = group.next().unwrap();
+                id.map(|id| Segment {
+                    id,
+                    start,
+                    len: group.count() + 1,
+                })
+            })
+            .collect();
+    }
+
+    // Moves whole files, highest id first, into the leftmost span that fits.
+    // Free spans are kept in one min-heap of start positions per span length.
+    pub fn compact_files(&mut self) {
+        let spans = self.free_spans();
+        let max_len = spans.iter().map(|&(_, len)| len).max().unwrap_or(0);
+        let mut free: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); max_len + 1];
+        for (start, len) in spans {
+            free[len].push(Reverse(start));
+        }
+        self.segments.sort_by_key(|seg| Reverse(seg.id));
+        for seg in self.segments.iter_mut() {
+            if seg.len > max_len {
+                continue;
+            }
+            let best = (seg.len..=max_len)
+                .filter_map(|len| free[len].peek().map(|&Reverse(start)| (start, len)))
+                .filter(|&(start, _)| start < seg.start)
+                .min();
+            if let Some((start, len)) = best {
+                free[len].pop();
+                seg.start = start;
+                if len > seg.len {
+                    free[len - seg.len].push(Reverse(start + seg.len));
+                }
+            }
+        }
+        self.segments.sort_by_key(|seg| seg.start);
+    }
+
+    pub fn checksum(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|seg| seg.id * (seg.start..seg.start + seg.len).sum::<usize>())
+            .sum()
+    }
+
+    // Puzzle notation such as "00...111...2", ids above 9 only show their last digit
+    pub fn render(&self) -> String {
+        self.blocks()
+            .iter()
+            .map(|block| match block {
+                Some(id) => char::from_digit((id % 10) as u32, 10).unwrap(),
+                None => '.',
+            })
+            .collect()
+    }
+}
+
+#[aoc(day9, part1, DISK)]
+pub(crate) fn solver_part1_disk(input: &Input) -> usize {
+    let mut disk = Disk::parse(input);
+    disk.compact_blocks();
+    disk.checksum()
+}
+
+#[aoc(day9, part2, DISK)]
+pub(crate) fn solver_part2_disk(input: &Input) -> usize {
+    let mut disk = Disk::parse(input);
+    disk.compact_files();
+    disk.checksum()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day9::solver_part1_inline;
 
     use super::input_generator;
     use super::solver_part1;
+    use super::solver_part1_disk;
     use super::solver_part2;
+    use super::solver_part2_disk;
+    use super::Disk;
 
     static INPUT: &str = "2333133121414131402";
 
@@ -305,4 +470,33 @@ mod tests {
     fn part2() {
         assert_eq!(solver_part2(&input_generator(INPUT)), 2858)
     }
+
+    #[test]
+    fn part1_disk() {
+        assert_eq!(solver_part1_disk(&input_generator(INPUT)), 1928)
+    }
+
+    #[test]
+    fn part2_disk() {
+        assert_eq!(solver_part2_disk(&input_generator(INPUT)), 2858)
+    }
+
+    #[test]
+    fn render() {
+        let disk = Disk::parse(INPUT);
+        assert_eq!(disk.render(), "00...111...2...333.44.5555.6666.777.888899");
+
+        let mut blocks = disk.clone();
+        blocks.compact_blocks();
+        assert_eq!(
+            blocks.render(),
+            "0099811188827773336446555566.............."
+        );
+
+        let mut files = disk.clone();
+        files.compact_files();
+        assert_eq!(files.render(), "00992111777.44.333....5555.6666.....8888..");
+
+        assert_eq!(Disk::parse("12345").render(), "0..111....22222");
+    }
 }
@@ -0,0 +1,165 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::season::Day;
+
+pub const YEAR: u16 = 2024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validation {
+    Valid,
+    Missing,
+    Unreadable(String),
+    // What the day's parser objected to
+    Invalid(String),
+}
+
+// Inputs live at `<root>/<year>/day<N>.txt`, with each teammate's under `<root>/<year>/<profile>/`
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputStore { root: root.into() }
+    }
+
+    fn dir(&self, year: u16, profile: Option<&str>) -> PathBuf {
+        let dir = self.root.join(year.to_string());
+        match profile {
+            Some(profile) => dir.join(profile),
+            None => dir,
+        }
+    }
+
+    pub fn path(&self, year: u16, day: u8, profile: Option<&str>) -> PathBuf {
+        self.dir(year, profile).join(format!("day{day}.txt"))
+    }
+
+    pub fn answers_path(&self, year: u16, profile: Option<&str>) -> PathBuf {
+        self.dir(year, profile).join("answers.txt")
+    }
+
+    pub fn read(&self, year: u16, day: u8, profile: Option<&str>) -> io::Result<String> {
+        fs::read_to_string(self.path(year, day, profile))
+    }
+
+    // Named profiles for the year, the default inputs are not included
+    pub fn profiles(&self, year: u16) -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(self.dir(year, None)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                profiles.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        profiles.sort();
+        Ok(profiles)
+    }
+
+    pub fn missing(&self, year: u16, profile: Option<&str>) -> Vec<u8> {
+        (1..=25)
+            .filter(|&day| !self.path(year, day, profile).is_file())
+            .collect()
+    }
+
+    // Runs each day's parse check over its input
+    pub fn validate(
+        &self,
+        year: u16,
+        profile: Option<&str>,
+        days: &[Day],
+    ) -> Vec<(u8, Validation)> {
+        days.iter()
+            .map(|day| {
+                let validation = match self.read(year, day.day, profile) {
+                    Ok(input) if input.trim().is_empty() => {
+                        Validation::Invalid("empty".to_string())
+                    }
+                    Ok(input) => match (day.check)(&input) {
+                        Ok(()) => Validation::Valid,
+                        Err(e) => Validation::Invalid(e),
+                    },
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => Validation::Missing,
+                    Err(e) => Validation::Unreadable(e.to_string()),
+                };
+                (day.day, validation)
+            })
+            .collect()
+    }
+}
+
+impl Default for InputStore {
+    fn default() -> Self {
+        InputStore::new("input")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputStore;
+    use super::Validation;
+    use crate::season::days;
+    use std::fs;
+
+    #[test]
+    fn store() {
+        let root = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        let store = InputStore::new(&root);
+        assert_eq!(store.path(2024, 3, None), root.join("2024/day3.txt"));
+        assert_eq!(
+            store.path(2024, 3, Some("alice")),
+            root.join("2024/alice/day3.txt")
+        );
+        assert_eq!(store.missing(2024, None).len(), 25);
+        assert!(store.profiles(2024).unwrap().is_empty());
+
+        fs::create_dir_all(root.join("2024/alice")).unwrap();
+        fs::create_dir_all(root.join("2024/bob")).unwrap();
+        fs::write(store.path(2024, 1, Some("alice")), "3   4\n4   3\n").unwrap();
+        fs::write(store.path(2024, 13, Some("alice")), "not a claw machine").unwrap();
+        // Days 7 and 25 keep the input as a String until a solver parses it
+        fs::write(
+            store.path(2024, 7, Some("alice")),
+            "190: 10 19\n3267 81 40 27\n",
+        )
+        .unwrap();
+        fs::write(store.path(2024, 25, Some("alice")), "###\n#..\n#..\n#..").unwrap();
+        fs::write(store.path(2024, 22, Some("alice")), "\n").unwrap();
+        fs::write(store.path(2024, 23, Some("alice")), "kh-tc\nqp-KH\n").unwrap();
+        assert_eq!(store.profiles(2024).unwrap(), ["alice", "bob"]);
+        assert_eq!(store.missing(2024, Some("alice")).len(), 19);
+        assert_eq!(
+            store.read(2024, 1, Some("alice")).unwrap(),
+            "3   4\n4   3\n"
+        );
+
+        let validation = store.validate(2024, Some("alice"), &days());
+        assert_eq!(validation[0], (1, Validation::Valid));
+        assert_eq!(validation[1], (2, Validation::Missing));
+        assert!(matches!(validation[12], (13, Validation::Invalid(_))));
+        assert!(matches!(validation[6], (7, Validation::Invalid(_))));
+        assert_eq!(
+            validation[24],
+            (25, Validation::Invalid("PinTooLong(0)".to_string()))
+        );
+        assert_eq!(
+            validation[22],
+            (
+                23,
+                Validation::Invalid("Connection \"qp-KH\" isn't two lowercase names".to_string())
+            )
+        );
+        assert_eq!(
+            validation[21],
+            (22, Validation::Invalid("empty".to_string()))
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
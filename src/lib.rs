@@ -28,5 +28,7 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+pub mod inputs;
+pub mod season;
 
 aoc_lib! { year = 2024 }
@@ -0,0 +1,1076 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+pub type Solver = fn(&str) -> String;
+
+// Parses an input with the day's own parser, describing the first problem found
+pub type Check = fn(&str) -> Result<(), String>;
+
+// How long a variant may run before it's left out of the fastest column
+pub const VARIANT_BUDGET: Duration = Duration::from_secs(10);
+
+pub struct Variant {
+    pub name: &'static str,
+    pub run: Solver,
+}
+
+// The first variant of each part is the default solver
+pub struct Day {
+    pub day: u8,
+    pub check: Check,
+    pub parts: [Vec<Variant>; 2],
+}
+
+macro_rules! variant {
+    ($day:ident, $name:literal, $solver:ident) => {
+        Variant {
+            name: $name,
+            run: |input| crate::$day::$solver(&crate::$day::input_generator(input)).to_string(),
+        }
+    };
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+// Keeps panics out of stderr while a solver or parser runs, they're reported instead.
+// Any other panic goes to the hook that was installed before
+pub fn quiet_solver_panics() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !QUIET.get() {
+            previous(info);
+        }
+    }));
+}
+
+fn quietly<T>(f: impl FnOnce() -> T) -> thread::Result<T> {
+    QUIET.set(true);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    QUIET.set(false);
+    result
+}
+
+// Most parsers unwrap, so their panic message is the error
+fn parses<T>(parse: impl FnOnce() -> T) -> Result<(), String> {
+    quietly(parse).map(drop).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "parser panicked".to_string())
+    })
+}
+
+// Equal length lines of allowed bytes
+fn grid(input: &str, allowed: fn(u8) -> bool) -> Result<(), String> {
+    let width = input.lines().next().map_or(0, |l| l.len());
+    for (y, line) in input.lines().enumerate() {
+        if line.len() != width {
+            return Err(format!(
+                "line {} is {} wide, expected {width}",
+                y + 1,
+                line.len()
+            ));
+        }
+        if let Some(b) = line.bytes().find(|&b| !allowed(b)) {
+            return Err(format!("line {} has unexpected {:?}", y + 1, b as char));
+        }
+    }
+    Ok(())
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day {
+            day: 1,
+            check: |input| {
+                let mut lists = crate::day1::LocationLists::<i64>::new();
+                lists
+                    .ingest(input.as_bytes())
+                    .map(drop)
+                    .map_err(|e| e.to_string())
+            },
+            parts: [
+                vec![
+                    variant!(day1, "default", solver_part1),
+                    variant!(day1, "UNSTABLE", solver_part1_unstable),
+                    variant!(day1, "BHEAP", solver_part1_bheap),
+                    variant!(day1, "UNSTABLE_I32", solver_part1_unstable_i32),
+                    variant!(
+                        day1,
+                        "UNSTABLE_I32_CUSTOM_PARSE",
+                        solver_part1_unstable_i32_custom_parse
+                    ),
+                    variant!(
+                        day1,
+                        "UNSTABLE_I32_CUSTOM_PARSE_2",
+                        solver_part1_unstable_i32_custom_parse_2
+                    ),
+                    variant!(
+                        day1,
+                        "UNSTABLE_I32_PARALLEL",
+                        solver_part1_unstable_i32_parallel
+                    ),
+                    variant!(day1, "UNSTABLE_I32_FAST", solver_part1_unstable_i32_fast),
+                    variant!(day1, "STREAM", solver_part1_stream),
+                ],
+                vec![
+                    variant!(day1, "default", solver_part2),
+                    variant!(day1, "HASH", solver_part2_hash),
+                    variant!(day1, "HASH_INLINE", solver_part2_hash_inline),
+                    variant!(day1, "STREAM", solver_part2_stream),
+                ],
+            ],
+        },
+        Day {
+            day: 2,
+            check: |input| parses(|| crate::day2::parse(input)),
+            parts: [
+                vec![
+                    variant!(day2, "default", solver_part1),
+                    variant!(day2, "FAST", solver_part1_fast),
+                    variant!(day2, "FAST_PARSE", solver_part1_fast_parse),
+                    variant!(day2, "FAST_PARSE_CUSTOM", solver_part1_fast_parse_custom),
+                    variant!(day2, "FAST_INLINE", solver_part1_fast_inline),
+                    variant!(day2, "FAST_INLINE_2", solver_part1_fast_inline_2),
+                    variant!(day2, "ANALYSER", solver_part1_analyser),
+                ],
+                vec![
+                    variant!(day2, "default", solver_part2),
+                    variant!(day2, "FAST", solver_part2_fast),
+                    variant!(day2, "ANALYSER", solver_part2_analyser),
+                ],
+            ],
+        },
+        Day {
+            day: 3,
+            check: |input| {
+                let mut scanner = crate::day3::Scanner::new(input.as_bytes());
+                scanner
+                    .try_for_each(|t| t.map(drop))
+                    .map_err(|e| e.to_string())
+            },
+            parts: [
+                vec![
+                    variant!(day3, "default", solver_part1),
+                    variant!(day3, "DFA", solver_part1_dfa),
+                    variant!(day3, "DFA2", solver_part1_dfa2),
+                    variant!(day3, "STREAM", solver_part1_stream),
+                ],
+                vec![
+                    variant!(day3, "default", solver_part2),
+                    variant!(day3, "SINGLE", solver_part2_single),
+                    variant!(day3, "DFA", solver_part2_dfa),
+                    variant!(day3, "STREAM", solver_part2_stream),
+                ],
+            ],
+        },
+        Day {
+            day: 4,
+            check: |input| {
+                grid(input, |b| b.is_ascii_uppercase())?;
+                parses(|| crate::day4::parse_byte(input))
+            },
+            parts: [
+                vec![
+                    variant!(day4, "default", solver_part1),
+                    variant!(day4, "FAST", solver_part1_fast),
+                    variant!(day4, "FAST_FLATTEN", solver_part1_fast_flatten),
+                    variant!(day4, "FAST_FLATTEN_BYTES", solver_part1_fast_flatten_bytes),
+                    variant!(
+                        day4,
+                        "FAST_FLATTEN_BYTES_FILTER",
+                        solver_part1_fast_flatten_bytes_filter
+                    ),
+                    variant!(day4, "SEARCH", solver_part1_search),
+                ],
+                vec![
+                    variant!(day4, "default", solver_part2),
+                    variant!(day4, "ITER", solver_part2_iter),
+                    variant!(day4, "ITER_ARRAY", solver_part2_iter_array),
+                    variant!(day4, "ITER_INNER", solver_part2_iter_inner),
+                    variant!(day4, "ITER_INNER_ARRAY", solver_part2_iter_inner_array),
+                    variant!(day4, "SEARCH", solver_part2_search),
+                ],
+            ],
+        },
+        Day {
+            day: 5,
+            check: |input| parses(|| crate::day5::RuleEngine::parse(input)),
+            parts: [
+                vec![
+                    variant!(day5, "default", solver_part1),
+                    variant!(day5, "HASH", solver_part1_hash),
+                    variant!(day5, "HASH_REDUCED", solver_part1_hash_reduced),
+                    variant!(day5, "FAST_HASH_REDUCED", solver_part1_fast_hash_reduced),
+                    variant!(
+                        day5,
+                        "FAST_HASH_REDUCED_INLINE",
+                        solver_part1_fast_hash_reduced_line
+                    ),
+                    variant!(
+                        day5,
+                        "FAST_HASH_INLINE_SORT",
+                        solver_part1_fast_hash_inline_sort
+                    ),
+                    variant!(
+                        day5,
+                        "FAST_HASH_SET_INLINE_SORT",
+                        solver_part1_fast_hash_set_inline_sort
+                    ),
+                    variant!(
+                        day5,
+                        "FAST_HASH_ARRAY_INLINE_SORT",
+                        solver_part1_fast_hash_array_inline_sort
+                    ),
+                    variant!(day5, "RULE_ENGINE", solver_part1_rule_engine),
+                ],
+                vec![
+                    variant!(day5, "default", solver_part2),
+                    variant!(day5, "HASH", solver_part2_hash),
+                    variant!(day5, "FAST_HASH_INLINE", solver_part2_fast_hash_inline),
+                    variant!(
+                        day5,
+                        "FAST_HASH_INLINE_SORT",
+                        solver_part2_fast_hash_inline_sort
+                    ),
+                    variant!(
+                        day5,
+                        "FAST_HASH_INLINE_SORT_NTH",
+                        solver_part2_fast_hash_inline_sort_nth
+                    ),
+                    variant!(
+                        day5,
+                        "FAST_HASH_ARRAY_INLINE_SORT_NTH",
+                        solver_part2_fast_hash_array_inline_sort_nth
+                    ),
+                    variant!(day5, "RULE_ENGINE", solver_part2_rule_engine),
+                ],
+            ],
+        },
+        Day {
+            day: 6,
+            check: |input| {
+                grid(input, |b| b".#^>v<".contains(&b))?;
+                parses(|| crate::day6::input_generator(input))
+            },
+            parts: [
+                vec![
+                    variant!(day6, "default", solver_part1),
+                    variant!(day6, "PATROL", solver_part1_patrol),
+                ],
+                vec![
+                    variant!(day6, "default", solver_part2),
+                    variant!(day6, "VISITED", solver_part2_visited),
+                    variant!(day6, "VISITED_PARALLEL", solver_part2_parallel),
+                    variant!(day6, "VISITED_PARALLEl_SHORT", solver_part2_short),
+                    variant!(day6, "PATROL", solver_part2_patrol),
+                ],
+            ],
+        },
+        Day {
+            day: 7,
+            check: |input| {
+                parses(|| {
+                    input
+                        .lines()
+                        .map(crate::day7::Equation::parse)
+                        .for_each(drop)
+                })
+            },
+            parts: [
+                vec![
+                    variant!(day7, "default", solver_part1),
+                    variant!(day7, "EARLY_EXIT", solver_part1_early_exit),
+                    variant!(day7, "RECURSIVE", solver_part1_recursive),
+                    variant!(day7, "RECURSIVE_PARALLEL", solver_part1_recursive_paralell),
+                    variant!(day7, "RECURSIVE_SWITCH", solver_part1_recursive_switch),
+                    variant!(day7, "OPERATOR_SEARCH", solver_part1_operator_search),
+                ],
+                vec![
+                    variant!(day7, "default", solver_part2),
+                    variant!(day7, "FAST_CONCAT", solver_part2_fast_concat),
+                    variant!(day7, "FASTER_CONCAT", solver_part2_faster_concat),
+                    variant!(
+                        day7,
+                        "FASTER_CONCAT_EARLY_EXIT",
+                        solver_part2_faster_concat_early_exit
+                    ),
+                    variant!(day7, "RECURSIVE", solver_part2_recursive),
+                    variant!(day7, "OPERATOR_SEARCH", solver_part2_operator_search),
+                ],
+            ],
+        },
+        Day {
+            day: 8,
+            check: |input| {
+                grid(input, |b| b == b'.' || b.is_ascii_alphanumeric())?;
+                parses(|| crate::day8::Antennas::parse(input))
+            },
+            parts: [
+                vec![
+                    variant!(day8, "default", solver_part1),
+                    variant!(day8, "ENGINE", solver_part1_engine),
+                ],
+                vec![
+                    variant!(day8, "default", solver_part2),
+                    variant!(day8, "ENGINE", solver_part2_engine),
+                ],
+            ],
+        },
+        Day {
+            day: 9,
+            check: |input| {
+                grid(input.trim(), |b| b.is_ascii_digit())?;
+                parses(|| crate::day9::Disk::parse(input))
+            },
+            parts: [
+                vec![
+                    variant!(day9, "default", solver_part1),
+                    variant!(day9, "INLINE", solver_part1_inline),
+                    variant!(day9, "DISK", solver_part1_disk),
+                ],
+                vec![
+                    variant!(day9, "default", solver_part2),
+                    variant!(day9, "SHORT", solver_part2_short),
+                    variant!(day9, "DISK", solver_part2_disk),
+                ],
+            ],
+        },
+        Day {
+            day: 10,
+            check: |input| {
+                grid(input, |b| b == b'.' || b.is_ascii_digit())?;
+                parses(|| crate::day10::input_generator(input))
+            },
+            parts: [
+                vec![
+                    variant!(day10, "default", solver_part1),
+                    variant!(day10, "CACHE", solver_part1_cache),
+                    variant!(day10, "CACHE_FX", solver_part1_cache_fx),
+                    variant!(day10, "CACHE_FX_VEC", solver_part1_cache_fx_vec),
+                    variant!(day10, "TRAILS", solver_part1_trails),
+                ],
+                vec![
+                    variant!(day10, "default", solver_part2),
+                    variant!(day10, "CACHE", solver_part2_cache),
+                    variant!(day10, "TRAILS", solver_part2_trails),
+                ],
+            ],
+        },
+        Day {
+            day: 11,
+            check: |input| parses(|| crate::day11::parse_stones::<u64>(input)),
+            parts: [
+                vec![
+                    variant!(day11, "default", solver_part1),
+                    variant!(day11, "BFS", solver_part1_bfs),
+                    variant!(day11, "Parallel", solver_part1_parallel),
+                    variant!(day11, "FX_Custom", solver_part1_fx_custom),
+                    variant!(day11, "Parallel_FX", solver_part1_parallel_fx),
+                    variant!(day11, "Parallel_FX_Custom", solver_part1_parallel_fx_custom),
+                    variant!(day11, "RULES", solver_part1_rules),
+                ],
+                vec![
+                    variant!(day11, "default", solver_part2),
+                    variant!(day11, "Parallel_Cache", solver_part2_parallel_cache),
+                    variant!(day11, "FX_Custom", solver_part2_fx_custom),
+                    variant!(day11, "Parallel_FX", solver_part2_parallel_fx),
+                    variant!(day11, "Parallel_FX_Custom", solver_part2_parallel_fx_custom),
+                    variant!(day11, "RULES", solver_part2_rules),
+                ],
+            ],
+        },
+        Day {
+            day: 12,
+            check: |input| {
+                grid(input, |b| b.is_ascii_uppercase())?;
+                parses(|| crate::day12::parse_grid(input))
+            },
+            parts: [
+                vec![
+                    variant!(day12, "default", solver_part1),
+                    variant!(day12, "REGIONS", solver_part1_regions),
+                ],
+                vec![
+                    variant!(day12, "default", solver_part2),
+                    variant!(day12, "REGIONS", solver_part2_regions),
+                ],
+            ],
+        },
+        Day {
+            day: 13,
+            check: |input| parses(|| crate::day13::input_generator(input)),
+            parts: [
+                vec![
+                    variant!(day13, "default", solver_part1),
+                    variant!(day13, "EXACT", solver_part1_exact),
+                ],
+                vec![
+                    variant!(day13, "default", solver_part2),
+                    variant!(day13, "EXACT", solver_part2_exact),
+                ],
+            ],
+        },
+        Day {
+            day: 14,
+            check: |input| parses(|| crate::day14::Room::parse(input, 101, 103)),
+            parts: [
+                vec![
+                    variant!(day14, "default", solver_part1),
+                    variant!(day14, "REM", solver_part1_rem),
+                    variant!(day14, "ROOM", solver_part1_room),
+                ],
+                vec![
+                    variant!(day14, "default", solver_part2),
+                    variant!(day14, "BORDER", solver_part2_border),
+                    variant!(day14, "VARIANCE", solver_part2_variance),
+                    variant!(day14, "ENTROPY", solver_part2_entropy),
+                    variant!(day14, "RUN_LENGTH", solver_part2_run_length),
+                ],
+            ],
+        },
+        Day {
+            day: 15,
+            check: |input| {
+                let map = input.split("\n\n").next().unwrap_or_default();
+                grid(map, |b| b"#.O@".contains(&b))?;
+                parses(|| crate::day15::Warehouse::parse(input, 1))
+            },
+            parts: [
+                vec![
+                    variant!(day15, "default", solver_part1),
+                    variant!(day15, "SIMULATOR", solver_part1_simulator),
+                ],
+                vec![
+                    variant!(day15, "default", solver_part2),
+                    variant!(day15, "SIMULATOR", solver_part2_simulator),
+                ],
+            ],
+        },
+        Day {
+            day: 16,
+            check: |input| {
+                let maze = crate::day16::Maze::parse(input);
+                maze.map(drop).map_err(|e| format!("{e:?}"))
+            },
+            parts: [
+                vec![
+                    variant!(day16, "default", solver_part1),
+                    variant!(day16, "MAP", solver_part1_map),
+                    variant!(day16, "SOLVER", solver_part1_solver),
+                ],
+                vec![
+                    variant!(day16, "default", solver_part2),
+                    variant!(day16, "SOLVER", solver_part2_solver),
+                ],
+            ],
+        },
+        Day {
+            day: 17,
+            check: |input| parses(|| crate::day17::parse_input(input)),
+            parts: [
+                vec![
+                    variant!(day17, "default", solver_part1),
+                    variant!(day17, "ITER", solver_part1_iter),
+                    variant!(day17, "SHIFT", solver_part1_shift),
+                    variant!(day17, "SHIFT_FAST_PARSE", solver_part1_shift_fast_parse),
+                    variant!(day17, "SHIFT_FASTER_PARSE", solver_part1_shift_faster_parse),
+                ],
+                vec![
+                    variant!(day17, "default", solver_part2),
+                    variant!(day17, "BACKWARDS", solver_part2_backwrds),
+                    variant!(day17, "BACKWARDS_FASTER", solver_part2_backwrds_faster),
+                ],
+            ],
+        },
+        Day {
+            day: 18,
+            check: |input| parses(|| crate::day18::Memory::parse(input, 71)),
+            parts: [
+                vec![
+                    variant!(day18, "default", solver_part1),
+                    variant!(day18, "BFS", solver_part1_bfs),
+                ],
+                vec![
+                    variant!(day18, "default", solver_part2),
+                    variant!(day18, "UNION_FIND", solver_part2_union_find),
+                    variant!(day18, "BINARY_SEARCH", solver_part2_binary_search),
+                ],
+            ],
+        },
+        Day {
+            day: 19,
+            check: |input| parses(|| crate::day19::TowelIndex::parse(input)),
+            parts: [
+                vec![
+                    variant!(day19, "default", solver_part1),
+                    variant!(day19, "PARALLEL", solver_part1_parallel),
+                    variant!(day19, "PARALLEL_DASH", solver_part1_parallel_dash),
+                    variant!(day19, "TRIE", solver_part1_trie),
+                ],
+                vec![
+                    variant!(day19, "default", solver_part2),
+                    variant!(day19, "PARALLEL", solver_part2_parallel),
+                    variant!(day19, "PARALLEL_DASH", solver_part2_parallel_dash),
+                    variant!(day19, "PARALLEL_SCC", solver_part2_parallel_scc),
+                    variant!(day19, "PARALLEL_WITH", solver_part2_parallel_with),
+                    variant!(day19, "PARALLEL_CHUNKS", solver_part2_parallel_chunks),
+                    variant!(day19, "PARALLEL_WITH_FX", solver_part2_parallel_with_fx),
+                    variant!(day19, "PARALLEL_MANUAL", solver_part2_parallel_manual),
+                    variant!(day19, "TRIE", solver_part2_trie),
+                ],
+            ],
+        },
+        Day {
+            day: 20,
+            check: |input| {
+                grid(input, |b| b"#.SE".contains(&b))?;
+                parses(|| crate::day20::CheatAnalyser::new(input))
+            },
+            parts: [
+                vec![
+                    variant!(day20, "default", solver_part1),
+                    variant!(day20, "FASTER", solver_part1_faster),
+                    variant!(day20, "I16", solver_part1_i16),
+                    variant!(day20, "ALT", solver_part1_alt),
+                    variant!(day20, "ANALYSER", solver_part1_analyser),
+                ],
+                vec![
+                    variant!(day20, "default", solver_part2),
+                    variant!(day20, "PARALLEL", solver_part2_parallel),
+                    variant!(day20, "PARALLEL_3", solver_part2_parallel_3),
+                    variant!(day20, "PARALLEL_4", solver_part2_parallel_4),
+                    variant!(day20, "PARALLEL_2", solver_part2_parallel_2),
+                    variant!(day20, "FULL_SLICE", solver_part2_full_slice),
+                    variant!(day20, "WORSE", solver_part2_worse),
+                    variant!(day20, "SLICE", solver_part2_slice),
+                    variant!(day20, "ANALYSER", solver_part2_analyser),
+                ],
+            ],
+        },
+        Day {
+            day: 21,
+            check: |input| {
+                parses(|| {
+                    let chain = crate::day21::KeypadChain::new(
+                        crate::day21::Keypad::numeric(),
+                        crate::day21::Keypad::directional(),
+                        2,
+                    );
+                    input
+                        .lines()
+                        .map(|code| chain.complexity(code))
+                        .for_each(drop)
+                })
+            },
+            parts: [
+                vec![
+                    variant!(day21, "default", solver_part1),
+                    variant!(day21, "FASTER", solver_part1_faster),
+                    variant!(day21, "Cache", solver_part1_cache),
+                    variant!(day21, "CHAIN", solver_part1_chain),
+                ],
+                vec![
+                    variant!(day21, "default", solver_part2),
+                    variant!(day21, "GRID_CACHE", solver_part2_grid_cache),
+                    variant!(day21, "FLAT_CACHE", solver_part2_flat_cache),
+                    variant!(day21, "CHAIN", solver_part2_chain),
+                ],
+            ],
+        },
+        Day {
+            day: 22,
+            check: |input| parses(|| crate::day22::parse_secrets(input)),
+            parts: [
+                vec![
+                    variant!(day22, "default", solver_part1),
+                    variant!(day22, "BULK", solver_part1_bulk),
+                    variant!(day22, "CHUNK", solver_part1_chunk),
+                    variant!(day22, "CHUNK_PARALLEL", solver_part1_chunk_parallel),
+                    variant!(day22, "SIMD", solver_part1_simd),
+                ],
+                vec![
+                    variant!(day22, "default", solver_part2),
+                    variant!(day22, "DENSE", solver_part2_dense),
+                ],
+            ],
+        },
+        Day {
+            day: 23,
+            check: |input| parses(|| input.lines().map(crate::day23::parse_link).for_each(drop)),
+            parts: [
+                vec![
+                    variant!(day23, "default", solver_part1),
+                    variant!(day23, "FASTER", solver_part1_faster),
+                    variant!(day23, "SORTED", solver_part1_sorted),
+                ],
+                vec![variant!(day23, "default", solver_part2)],
+            ],
+        },
+        Day {
+            day: 24,
+            check: |input| parses(|| crate::day24::parse(input)),
+            parts: [
+                vec![variant!(day24, "default", solver_part1)],
+                vec![variant!(day24, "default", solver_part2)],
+            ],
+        },
+        Day {
+            day: 25,
+            check: |input| {
+                let schematics = crate::day25::Schematics::parse(input);
+                schematics.map(drop).map_err(|e| format!("{e:?}"))
+            },
+            parts: [
+                vec![
+                    variant!(day25, "default", solver_part1),
+                    variant!(day25, "FITTER", solver_part1_fitter),
+                ],
+                vec![],
+            ],
+        },
+    ]
+}
+
+// Known answers, one "<day> <part> <answer>" per line. Blank lines and lines starting with '#' are skipped
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(HashMap<(u8, u8), String>);
+
+impl Answers {
+    pub fn parse(input: &str) -> Answers {
+        Answers(
+            input
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .filter_map(|l| {
+                    let mut fields = l.splitn(3, ' ');
+                    let day = fields.next()?.parse().ok()?;
+                    let part = fields.next()?.parse().ok()?;
+                    Some(((day, part), fields.next()?.trim().to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    // A missing file is the same as having no answers yet
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(input) => Ok(Answers::parse(&input)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(|s| s.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong(String),
+    Unchecked(String),
+    Panicked,
+    NoInput,
+    // Day 25 part 2 has no puzzle, its star comes with the other 49
+    Free(bool),
+}
+
+impl Outcome {
+    pub fn star(&self) -> bool {
+        matches!(self, Outcome::Correct | Outcome::Free(true))
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Outcome::Correct | Outcome::Free(true) => '*',
+            Outcome::Wrong(_) => 'x',
+            Outcome::Unchecked(_) => '?',
+            Outcome::Panicked => '!',
+            Outcome::NoInput | Outcome::Free(false) => '-',
+        }
+    }
+}
+
+pub struct PartReport {
+    pub outcome: Outcome,
+    pub time: Duration,
+    // The quickest variant giving the expected answer
+    pub fastest: Option<(&'static str, Duration)>,
+}
+
+pub struct DayReport {
+    pub day: u8,
+    pub parts: [PartReport; 2],
+}
+
+impl DayReport {
+    pub fn time(&self) -> Duration {
+        self.parts.iter().map(|p| p.time).sum()
+    }
+}
+
+fn time(solver: Solver, input: &str) -> (Option<String>, Duration) {
+    let start = Instant::now();
+    let answer = quietly(|| solver(input)).ok();
+    (answer, start.elapsed())
+}
+
+// Time limit on variants for a whole run. A thread can't be stopped, so a variant that overruns
+// keeps going in the background, and no later variant is timed against it for the cpu
+pub struct Budget {
+    limit: Duration,
+    overrun: Option<(u8, u8, &'static str)>,
+}
+
+impl Budget {
+    pub fn new(limit: Duration) -> Self {
+        Budget {
+            limit,
+            overrun: None,
+        }
+    }
+
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    // The day, part and name of the variant that stopped variant timing
+    pub fn overrun(&self) -> Option<(u8, u8, &'static str)> {
+        self.overrun
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::new(VARIANT_BUDGET)
+    }
+}
+
+enum Timed {
+    Answer(String, Duration),
+    Panicked,
+    Overran,
+}
+
+// Runs the solver on its own thread, giving up on it after `limit`
+fn time_within(solver: Solver, input: &str, limit: Duration) -> Timed {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    let spawned = thread::Builder::new()
+        // Some solvers recurse deeply
+        .stack_size(1 << 30)
+        .spawn(move || {
+            let _ = sender.send(time(solver, &input));
+        });
+    if spawned.is_err() {
+        return Timed::Panicked;
+    }
+    match receiver.recv_timeout(limit) {
+        Ok((Some(answer), time)) => Timed::Answer(answer, time),
+        Ok((None, _)) => Timed::Panicked,
+        Err(_) => Timed::Overran,
+    }
+}
+
+// Runs the default solver of each part, then with a budget every variant until one overruns.
+// The fastest column falls back to the default solver when variants aren't timed
+pub fn run_day(
+    day: &Day,
+    input: Option<&str>,
+    answers: &Answers,
+    mut budget: Option<&mut Budget>,
+) -> DayReport {
+    let parts = [1, 2].map(|part| {
+        let solvers = &day.parts[part as usize - 1];
+        let (Some(input), Some(default)) = (input, solvers.first()) else {
+            return PartReport {
+                outcome: Outcome::NoInput,
+                time: Duration::ZERO,
+                fastest: None,
+            };
+        };
+        let (answer, time) = time(default.run, input);
+        let expected = answers.get(day.day, part);
+        let outcome = match (&answer, expected) {
+            (None, _) => Outcome::Panicked,
+            (Some(answer), Some(expected)) if answer == expected => Outcome::Correct,
+            (Some(answer), Some(_)) => Outcome::Wrong(answer.clone()),
+            (Some(answer), None) => Outcome::Unchecked(answer.clone()),
+        };
+        let Some(expected) = expected.or(answer.as_deref()) else {
+            return PartReport {
+                outcome,
+                time,
+                fastest: None,
+            };
+        };
+        let mut fastest = (answer.as_deref() == Some(expected)).then_some((default.name, time));
+        for variant in solvers.iter().skip(1) {
+            let Some(budget) = budget.as_deref_mut().filter(|b| b.overrun.is_none()) else {
+                break;
+            };
+            match time_within(variant.run, input, budget.limit) {
+                Timed::Answer(answer, t)
+                    if answer == expected && fastest.is_none_or(|(_, best)| t < best) =>
+                {
+                    fastest = Some((variant.name, t));
+                }
+                Timed::Overran => budget.overrun = Some((day.day, part, variant.name)),
+                _ => (),
+            }
+        }
+        PartReport {
+            outcome,
+            time,
+            fastest,
+        }
+    });
+    DayReport {
+        day: day.day,
+        parts,
+    }
+}
+
+// Awards a part with no solvers once every other part has its star
+pub fn award_free_stars(reports: &mut [DayReport], days: &[Day]) {
+    let earned = reports
+        .iter()
+        .flat_map(|r| r.parts.iter())
+        .filter(|p| p.outcome.star())
+        .count();
+    let free = days
+        .iter()
+        .flat_map(|d| &d.parts)
+        .filter(|p| p.is_empty())
+        .count();
+    let all = earned + free == days.len() * 2;
+    for report in reports.iter_mut() {
+        let Some(day) = days.iter().find(|d| d.day == report.day) else {
+            continue;
+        };
+        for (part, solvers) in report.parts.iter_mut().zip(&day.parts) {
+            if solvers.is_empty() {
+                part.outcome = Outcome::Free(all);
+            }
+        }
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    if d >= Duration::from_secs(1) {
+        format!("{:.2}s", d.as_secs_f64())
+    } else if d >= Duration::from_millis(1) {
+        format!("{:.2}ms", d.as_secs_f64() * 1e3)
+    } else {
+        format!("{:.1}us", d.as_secs_f64() * 1e6)
+    }
+}
+
+pub fn render(reports: &[DayReport]) -> String {
+    let mut out = String::new();
+    let fastest = |p: &PartReport| match p.fastest {
+        Some((name, time)) => format!("{name} ({})", format_duration(time)),
+        None => String::new(),
+    };
+    let _ = writeln!(
+        out,
+        "{:>3}  {:<3} {:<3} {:>10}  {:<36} Fastest part 2",
+        "Day", "1", "2", "Time", "Fastest part 1"
+    );
+    for report in reports {
+        let [one, two] = &report.parts;
+        let _ = writeln!(
+            out,
+            "{:>3}  {:<3} {:<3} {:>10}  {:<36} {}",
+            report.day,
+            one.outcome.symbol(),
+            two.outcome.symbol(),
+            format_duration(report.time()),
+            fastest(one),
+            fastest(two),
+        );
+    }
+    for report in reports {
+        for (part, p) in report.parts.iter().enumerate() {
+            match &p.outcome {
+                Outcome::Wrong(answer) => {
+                    let _ = writeln!(
+                        out,
+                        "Day {} part {}: wrong answer {answer}",
+                        report.day,
+                        part + 1
+                    );
+                }
+                Outcome::Unchecked(answer) => {
+                    let _ = writeln!(
+                        out,
+                        "Day {} part {}: unchecked answer {answer}",
+                        report.day,
+                        part + 1
+                    );
+                }
+                _ => (),
+            }
+        }
+    }
+    let stars = reports
+        .iter()
+        .flat_map(|r| r.parts.iter())
+        .filter(|p| p.outcome.star())
+        .count();
+    let total = reports.iter().map(|r| r.time()).sum();
+    let _ = writeln!(
+        out,
+        "Stars: {stars}/{}  Total: {}",
+        reports.len() * 2,
+        format_duration(total)
+    );
+    let _ = writeln!(
+        out,
+        "* correct  x wrong  ? no stored answer  ! panicked  - no input"
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::award_free_stars;
+    use super::days;
+    use super::render;
+    use super::run_day;
+    use super::Answers;
+    use super::Budget;
+    use super::Day;
+    use super::Outcome;
+    use super::Variant;
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    // `#[aoc]` solvers left out of the table, day 11's part 2 Parallel is exponential and prints
+    // timings as it blinks
+    const EXCLUDED: &[(u8, u8, &str)] = &[(11, 2, "Parallel")];
+
+    static DAY1: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn answers() {
+        let answers =
+            Answers::parse("# day part answer\n1 1 11\n\n17 1 4,6,3,5,6,3,5,2,1,0\nbad\n");
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(17, 1), Some("4,6,3,5,6,3,5,2,1,0"));
+        assert_eq!(answers.get(1, 2), None);
+    }
+
+    #[test]
+    fn season() {
+        let days = days();
+        assert_eq!(days.len(), 25);
+        assert!(days[24].parts[1].is_empty());
+
+        let answers = Answers::parse("1 1 11\n1 2 30");
+        let report = run_day(&days[0], Some(DAY1), &answers, Some(&mut Budget::default()));
+        assert_eq!(report.parts[0].outcome, Outcome::Correct);
+        assert_eq!(report.parts[1].outcome, Outcome::Wrong("31".to_string()));
+        assert!(report.parts[0].fastest.is_some());
+        assert!(report.parts[1].fastest.is_none());
+
+        let mut reports = vec![report, run_day(&days[24], None, &answers, None)];
+        award_free_stars(&mut reports, &days);
+        assert_eq!(reports[1].parts[0].outcome, Outcome::NoInput);
+        assert_eq!(reports[1].parts[1].outcome, Outcome::Free(false));
+        let table = render(&reports);
+        assert!(table.contains("Stars: 1/4"));
+        assert!(table.contains("Day 1 part 2: wrong answer 31"));
+    }
+
+    #[test]
+    fn registry() {
+        // Each part lists the names of its #[aoc] attributes, the unnamed one first as "default"
+        for day in days() {
+            let path = format!("{}/src/day{}.rs", env!("CARGO_MANIFEST_DIR"), day.day);
+            let source = fs::read_to_string(path).unwrap();
+            for (part, variants) in day.parts.iter().enumerate() {
+                let part = part as u8 + 1;
+                let prefix = format!("#[aoc(day{}, part{part}", day.day);
+                let mut attributes = source
+                    .lines()
+                    .filter_map(|l| l.trim().strip_prefix(&prefix)?.strip_suffix(")]"))
+                    .map(|name| name.strip_prefix(", ").unwrap_or("default"))
+                    .filter(|&name| !EXCLUDED.contains(&(day.day, part, name)))
+                    .collect::<Vec<_>>();
+                let mut names = variants.iter().map(|v| v.name).collect::<Vec<_>>();
+                if let Some(&first) = names.first() {
+                    assert_eq!(first, "default", "day {} part {part}", day.day);
+                }
+                attributes.sort();
+                names.sort();
+                assert_eq!(names, attributes, "day {} part {part}", day.day);
+            }
+        }
+    }
+
+    #[test]
+    fn budget() {
+        let day = Day {
+            day: 1,
+            check: |_| Ok(()),
+            parts: [
+                vec![
+                    Variant {
+                        name: "default",
+                        run: |_| {
+                            thread::sleep(Duration::from_millis(5));
+                            "11".to_string()
+                        },
+                    },
+                    Variant {
+                        name: "SLOW",
+                        run: |_| {
+                            thread::sleep(Duration::from_secs(1));
+                            "11".to_string()
+                        },
+                    },
+                    Variant {
+                        name: "FAST",
+                        run: |_| "11".to_string(),
+                    },
+                ],
+                Vec::new(),
+            ],
+        };
+        let answers = Answers::parse("1 1 11");
+        let report = run_day(&day, Some(""), &answers, None);
+        assert_eq!(report.parts[0].outcome, Outcome::Correct);
+        assert_eq!(report.parts[0].fastest.unwrap().0, "default");
+
+        // FAST isn't timed while SLOW is still running
+        let mut budget = Budget::new(Duration::from_millis(100));
+        let report = run_day(&day, Some(""), &answers, Some(&mut budget));
+        assert_eq!(report.parts[0].fastest.unwrap().0, "default");
+        assert_eq!(budget.overrun(), Some((1, 1, "SLOW")));
+
+        let report = run_day(&day, Some(""), &Answers::parse("1 1 12"), None);
+        assert!(report.parts[0].fastest.is_none());
+    }
+}